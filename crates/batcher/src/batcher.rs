@@ -1,27 +1,50 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
+use blockifier::execution::call_info::{ExecutionSummary, PortableSummary};
 use blockifier::state::global_cache::GlobalContractCache;
+use blockifier::transaction::objects::TransactionExecutionInfo;
+use indexmap::IndexMap;
 #[cfg(test)]
 use mockall::automock;
 use papyrus_storage::state::{StateStorageReader, StateStorageWriter};
 use starknet_api::block::BlockNumber;
+use starknet_api::core::{GlobalRoot, StateDiffCommitment};
 use starknet_api::executable_transaction::Transaction;
+use starknet_api::hash::PoseidonHash;
 use starknet_api::state::ThinStateDiff;
+use starknet_api::transaction::TransactionHash;
 use starknet_batcher_types::batcher_types::{
     BatcherResult,
     BuildProposalInput,
     DecisionReachedInput,
+    DecisionReachedResponse,
+    EventFilter,
+    EventRecord,
     GetProposalContent,
     GetProposalContentInput,
     GetProposalContentResponse,
+    GetProposalStatusInput,
+    GetProposalStatusResponse,
+    PreConfirmInput,
+    PreConfirmation,
+    ProposalCommitment,
     ProposalId,
+    ProposalStatus,
+    RebuildProposalInput,
     StartHeightInput,
+    SummarizeTransactionsInput,
+    WarmUpHint,
 };
 use starknet_batcher_types::errors::BatcherError;
 use starknet_mempool_infra::component_definitions::ComponentStarter;
 use starknet_mempool_types::communication::SharedMempoolClient;
 use starknet_mempool_types::mempool_types::CommitBlockArgs;
+use tokio::sync::mpsc::error::TryRecvError;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::Stream;
 use tracing::{debug, error, info, instrument, trace};
 
 use crate::block_builder::BlockBuilderFactory;
@@ -37,6 +60,24 @@ use crate::proposal_manager::{
 
 struct Proposal {
     tx_stream: OutputStream,
+    // The sequence number of the next content chunk to stream; rewound when a stream is resumed.
+    next_sequence: u64,
+    // The end offsets of the content chunks streamed so far, by sequence, in the transactions
+    // streamed for the proposal (see `Batcher::proposal_txs`), for resuming dropped streams.
+    chunk_ends: Vec<usize>,
+    // The commitment of the proposal once all of its content was streamed. The proposal is kept
+    // until a decision is reached, so that a stream that dropped before its end can be resumed.
+    commitment: Option<ProposalCommitment>,
+}
+
+impl Proposal {
+    fn new(tx_stream: OutputStream) -> Self {
+        Self { tx_stream, next_sequence: 0, chunk_ends: Vec::new(), commitment: None }
+    }
+
+    fn n_sent_chunks(&self) -> u64 {
+        u64::try_from(self.chunk_ends.len()).expect("Chunk counts fit in u64.")
+    }
 }
 
 pub struct Batcher {
@@ -46,6 +87,30 @@ pub struct Batcher {
     pub mempool_client: SharedMempoolClient,
     proposal_manager: Box<dyn ProposalManagerTrait>,
     proposals: HashMap<ProposalId, Proposal>,
+    // Build requests received while another proposal was being built, waiting to be started by
+    // priority (and by arrival order within the same priority).
+    queued_proposals: Vec<BuildProposalInput>,
+    // The proposals started at the current height, including those no longer held in `proposals`.
+    known_proposal_ids: HashSet<ProposalId>,
+    // The queued proposals of the current height that failed to start (e.g., their deadline
+    // passed while queued).
+    failed_proposal_ids: HashSet<ProposalId>,
+    // The build requests of the proposals built at the current height, e.g., for echoing their
+    // metadata in their statuses.
+    proposal_inputs: HashMap<ProposalId, BuildProposalInput>,
+    // The transactions streamed for each proposal of the current height.
+    proposal_txs: HashMap<ProposalId, Vec<Transaction>>,
+    // The base proposals of the rebuilt proposals of the current height, whose transactions seed
+    // the rebuilt ones.
+    proposal_bases: HashMap<ProposalId, ProposalId>,
+    // The heights at which the proposals of the current height were committed, so that repeated
+    // decisions (e.g., retries) are not committed again. Kept in memory only, so decisions
+    // replayed after a restart or for a previous height are not recognized.
+    committed_proposal_heights: HashMap<ProposalId, BlockNumber>,
+    // The summaries of the transactions committed at the last few heights (see
+    // [N_HEIGHTS_WITH_TX_SUMMARIES]), oldest first.
+    committed_tx_summaries: VecDeque<(BlockNumber, HashMap<TransactionHash, ExecutionSummary>)>,
+    event_subscribers: Vec<(EventFilter, UnboundedSender<EventRecord>)>,
 }
 
 impl Batcher {
@@ -63,18 +128,124 @@ impl Batcher {
             mempool_client,
             proposal_manager,
             proposals: HashMap::new(),
+            queued_proposals: Vec::new(),
+            known_proposal_ids: HashSet::new(),
+            failed_proposal_ids: HashSet::new(),
+            proposal_inputs: HashMap::new(),
+            proposal_txs: HashMap::new(),
+            proposal_bases: HashMap::new(),
+            committed_proposal_heights: HashMap::new(),
+            committed_tx_summaries: VecDeque::new(),
+            event_subscribers: Vec::new(),
         }
     }
 
     pub async fn start_height(&mut self, input: StartHeightInput) -> BatcherResult<()> {
         self.proposals.clear();
+        self.queued_proposals.clear();
+        self.failed_proposal_ids.clear();
+        self.known_proposal_ids.clear();
+        self.proposal_inputs.clear();
+        self.proposal_txs.clear();
+        self.proposal_bases.clear();
+        self.committed_proposal_heights.clear();
         self.proposal_manager.start_height(input.height).await.map_err(BatcherError::from)
     }
 
+    /// Starts building the given proposal. If another proposal is being built, the request is
+    /// queued instead, and started once the batcher is free (see
+    /// [Self::start_next_queued_proposal]).
     #[instrument(skip(self), err)]
     pub async fn build_proposal(
         &mut self,
         build_proposal_input: BuildProposalInput,
+    ) -> BatcherResult<()> {
+        let proposal_id = build_proposal_input.proposal_id;
+        match self.start_proposal(&build_proposal_input).await {
+            Ok(()) => {}
+            Err(BatcherError::ServerBusy { active_proposal_id, new_proposal_id }) => {
+                debug!(
+                    "Queueing proposal {} behind active proposal {}.",
+                    new_proposal_id, active_proposal_id
+                );
+                self.known_proposal_ids.insert(new_proposal_id);
+                self.queued_proposals.push(build_proposal_input.clone());
+            }
+            Err(err) => return Err(err),
+        }
+        self.proposal_inputs.insert(proposal_id, build_proposal_input);
+        Ok(())
+    }
+
+    /// Starts building a new proposal, seeded by the transactions streamed so far for the base
+    /// proposal and with the base proposal's other parameters; see [Self::build_proposal]. The
+    /// base proposal must have been built at the current height.
+    #[instrument(skip(self), err)]
+    pub async fn rebuild_proposal(
+        &mut self,
+        rebuild_proposal_input: RebuildProposalInput,
+    ) -> BatcherResult<()> {
+        let RebuildProposalInput { proposal_id, base_proposal_id, deadline } =
+            rebuild_proposal_input;
+        let Some(base_input) = self.proposal_inputs.get(&base_proposal_id) else {
+            return Err(BatcherError::UnknownProposal { proposal_id: base_proposal_id });
+        };
+        let build_proposal_input =
+            BuildProposalInput { proposal_id, deadline, ..base_input.clone() };
+        self.proposal_bases.insert(proposal_id, base_proposal_id);
+        let result = self.build_proposal(build_proposal_input).await;
+        if result.is_err() {
+            self.proposal_bases.remove(&proposal_id);
+        }
+        result
+    }
+
+    /// Starts the queued proposal with the highest priority, if any, and returns its ID. Among
+    /// requests of the same priority, the earliest one is started first. A request that fails to
+    /// start leaves the queue and is marked as failed.
+    pub async fn start_next_queued_proposal(&mut self) -> BatcherResult<Option<ProposalId>> {
+        let Some(index) = self
+            .queued_proposals
+            .iter()
+            .enumerate()
+            .max_by_key(|(index, input)| (input.priority, Reverse(*index)))
+            .map(|(index, _)| index)
+        else {
+            return Ok(None);
+        };
+        let build_proposal_input = self.queued_proposals.remove(index);
+        match self.start_proposal(&build_proposal_input).await {
+            Ok(()) => Ok(Some(build_proposal_input.proposal_id)),
+            Err(err @ BatcherError::ServerBusy { .. }) => {
+                // Still busy; keep the request at its place in the queue.
+                self.queued_proposals.insert(index, build_proposal_input);
+                Err(err)
+            }
+            Err(err) => {
+                self.failed_proposal_ids.insert(build_proposal_input.proposal_id);
+                Err(err)
+            }
+        }
+    }
+
+    // Starts queued proposals until one of them is started, the batcher is busy, or the queue is
+    // empty.
+    async fn start_queued_proposals(&mut self) {
+        loop {
+            match self.start_next_queued_proposal().await {
+                Ok(_) => return,
+                Err(BatcherError::ServerBusy { active_proposal_id, .. }) => {
+                    debug!("Queued proposals wait for active proposal {}.", active_proposal_id);
+                    return;
+                }
+                Err(err) => error!("Failed to start a queued proposal: {}", err),
+            }
+        }
+    }
+
+    async fn start_proposal(
+        &mut self,
+        build_proposal_input: &BuildProposalInput,
     ) -> BatcherResult<()> {
         let proposal_id = build_proposal_input.proposal_id;
         let deadline =
@@ -82,20 +253,30 @@ impl Batcher {
                 |_| BatcherError::TimeToDeadlineError { deadline: build_proposal_input.deadline },
             )?);
 
+        // A rebuilt proposal starts from the transactions of its base proposal.
+        let seed_txs = self
+            .proposal_bases
+            .get(&proposal_id)
+            .and_then(|base_proposal_id| self.proposal_txs.get(base_proposal_id))
+            .cloned()
+            .unwrap_or_default();
         let (tx_sender, tx_receiver) = tokio::sync::mpsc::unbounded_channel();
 
         self.proposal_manager
             .build_block_proposal(
                 build_proposal_input.proposal_id,
-                build_proposal_input.retrospective_block_hash,
+                build_proposal_input.retrospective_block_hash.clone(),
                 deadline,
+                build_proposal_input.max_transactions,
+                build_proposal_input.resource_bounds,
+                seed_txs,
                 tx_sender,
             )
             .await
             .map_err(BatcherError::from)?;
 
-        let tx_stream = tx_receiver;
-        self.proposals.insert(proposal_id, Proposal { tx_stream });
+        self.proposals.insert(proposal_id, Proposal::new(tx_receiver));
+        self.known_proposal_ids.insert(proposal_id);
         Ok(())
     }
 
@@ -104,40 +285,161 @@ impl Batcher {
         &mut self,
         get_proposal_content_input: GetProposalContentInput,
     ) -> BatcherResult<GetProposalContentResponse> {
+        let response = self.next_proposal_content(get_proposal_content_input, true).await?;
+        Ok(response.expect("Content is always returned when waiting for it."))
+    }
+
+    /// Like [Self::get_proposal_content], but returns `None` instead of waiting if no content is
+    /// available yet.
+    #[instrument(skip(self), err)]
+    pub async fn try_get_proposal_content(
+        &mut self,
+        get_proposal_content_input: GetProposalContentInput,
+    ) -> BatcherResult<Option<GetProposalContentResponse>> {
+        self.next_proposal_content(get_proposal_content_input, false).await
+    }
+
+    // Returns the next content of the proposal. If `wait` is set, blocks until some transactions
+    // are available or the proposal is done; otherwise, returns `None` if there is no content yet.
+    // A queued proposal is started if the batcher is free; otherwise, it has no content yet and
+    // this returns without blocking, so that other requests are served meanwhile.
+    async fn next_proposal_content(
+        &mut self,
+        get_proposal_content_input: GetProposalContentInput,
+        wait: bool,
+    ) -> BatcherResult<Option<GetProposalContentResponse>> {
         let proposal_id = get_proposal_content_input.proposal_id;
 
-        let tx_stream = &mut self
-            .proposals
-            .get_mut(&proposal_id)
-            .ok_or(BatcherError::ProposalNotFound { proposal_id })?
-            .tx_stream;
+        if self.is_queued(proposal_id) {
+            // The active proposal may be done by now, without its content streamed to the end.
+            self.start_queued_proposals().await;
+            if self.is_queued(proposal_id) {
+                debug!("Proposal {} is queued and has no content yet.", proposal_id);
+                return Ok(no_content_yet(0, wait));
+            }
+        }
+        if self.failed_proposal_ids.contains(&proposal_id) {
+            return Err(BatcherError::ProposalFailed);
+        }
+
+        let Some(proposal) = self.proposals.get_mut(&proposal_id) else {
+            return Err(self.missing_proposal_error(proposal_id));
+        };
+
+        // Resuming rewinds the stream, so that the chunks after the resumed one are re-sent too.
+        if let Some(resume_from) = get_proposal_content_input.resume_from {
+            let n_sent_chunks = proposal.n_sent_chunks();
+            if resume_from > n_sent_chunks {
+                return Err(BatcherError::ResumeFromUnsentChunk {
+                    proposal_id,
+                    resume_from,
+                    n_sent_chunks,
+                });
+            }
+            proposal.next_sequence = resume_from;
+        }
+
+        let sequence = proposal.next_sequence;
+        if sequence < proposal.n_sent_chunks() {
+            debug!("Re-sending chunk {} of proposal {}.", sequence, proposal_id);
+            let chunk_index =
+                usize::try_from(sequence).expect("Sent chunks are indexed by their sequence.");
+            let chunk_start = chunk_index.checked_sub(1).map_or(0, |i| proposal.chunk_ends[i]);
+            let chunk_end = proposal.chunk_ends[chunk_index];
+            let txs = self.proposal_txs[&proposal_id][chunk_start..chunk_end].to_vec();
+            proposal.next_sequence += 1;
+            return Ok(Some(GetProposalContentResponse {
+                content: GetProposalContent::Txs(txs),
+                sequence,
+                is_last: false,
+            }));
+        }
+        if let Some(proposal_commitment) = proposal.commitment {
+            return Ok(Some(GetProposalContentResponse {
+                content: GetProposalContent::Finished(proposal_commitment),
+                sequence,
+                is_last: true,
+            }));
+        }
 
-        // Blocking until we have some txs to stream or the proposal is done.
+        let buffer_size = self.config.outstream_content_buffer_size;
+        let tx_stream = &mut proposal.tx_stream;
         let mut txs = Vec::new();
-        let n_executed_txs =
-            tx_stream.recv_many(&mut txs, self.config.outstream_content_buffer_size).await;
+        let n_executed_txs = if wait {
+            // Blocking until we have some txs to stream or the proposal is done.
+            tx_stream.recv_many(&mut txs, buffer_size).await
+        } else {
+            while txs.len() < buffer_size {
+                match tx_stream.try_recv() {
+                    Ok(tx) => txs.push(tx),
+                    Err(TryRecvError::Empty) if txs.is_empty() => return Ok(None),
+                    // Either no more txs for now, or the proposal is done.
+                    Err(_) => break,
+                }
+            }
+            txs.len()
+        };
 
         if n_executed_txs != 0 {
             debug!("Streaming {} txs", n_executed_txs);
-            return Ok(GetProposalContentResponse { content: GetProposalContent::Txs(txs) });
+            let proposal_txs = self.proposal_txs.entry(proposal_id).or_default();
+            proposal_txs.extend(txs.iter().cloned());
+            proposal.chunk_ends.push(proposal_txs.len());
+            proposal.next_sequence += 1;
+            return Ok(Some(GetProposalContentResponse {
+                content: GetProposalContent::Txs(txs),
+                sequence,
+                is_last: false,
+            }));
         }
 
         // Finished streaming all the transactions.
+        let proposal_commitment =
+            match self.proposal_manager.get_done_proposal_commitment(proposal_id).await {
+                Ok(proposal_commitment) => proposal_commitment,
+                // The stream is closed but the proposal was not marked as done yet.
+                Err(GetProposalResultError::ProposalDoesNotExist { .. }) => {
+                    debug!("Proposal {} has no content yet.", proposal_id);
+                    return Ok(no_content_yet(sequence, wait));
+                }
+                Err(err) => {
+                    self.proposals.remove(&proposal_id);
+                    return Err(err.into());
+                }
+            };
         // TODO: Consider removing the proposal from the proposal manager and keep it in the batcher
         // for decision reached.
-        self.proposals.remove(&proposal_id);
-        let proposal_commitment =
-            self.proposal_manager.get_done_proposal_commitment(proposal_id).await?;
-        Ok(GetProposalContentResponse {
+        proposal.commitment = Some(proposal_commitment);
+        // The batcher is free now; start the next queued proposal, if any.
+        self.start_queued_proposals().await;
+        Ok(Some(GetProposalContentResponse {
             content: GetProposalContent::Finished(proposal_commitment),
-        })
+            sequence,
+            is_last: true,
+        }))
     }
 
     #[instrument(skip(self), err)]
-    pub async fn decision_reached(&mut self, input: DecisionReachedInput) -> BatcherResult<()> {
+    pub async fn decision_reached(
+        &mut self,
+        input: DecisionReachedInput,
+    ) -> BatcherResult<DecisionReachedResponse> {
         let proposal_id = input.proposal_id;
-        let proposal_output = self.proposal_manager.take_proposal_result(proposal_id).await?;
-        let ProposalOutput { state_diff, nonces, tx_hashes, .. } = proposal_output;
+        if let Some(&height) = self.committed_proposal_heights.get(&proposal_id) {
+            info!("Proposal {} was already committed at height {}.", proposal_id, height);
+            return Ok(DecisionReachedResponse { already_committed: true, height });
+        }
+        let proposal_output = match self.proposal_manager.take_proposal_result(proposal_id).await {
+            Ok(proposal_output) => proposal_output,
+            Err(GetProposalResultError::ProposalDoesNotExist { proposal_id })
+                if !self.known_proposal_ids.contains(&proposal_id) =>
+            {
+                return Err(BatcherError::UnknownProposal { proposal_id });
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let ProposalOutput { state_diff, nonces, tx_hashes, execution_infos, events, .. } =
+            proposal_output;
         // TODO: Keep the height from start_height or get it from the input.
         let height = self.storage_reader.height().map_err(|err| {
             error!("Failed to get height from storage: {}", err);
@@ -152,14 +454,177 @@ impl Batcher {
             error!("Failed to commit proposal to storage: {}", err);
             BatcherError::InternalError
         })?;
+        self.committed_proposal_heights.insert(proposal_id, height);
+        self.proposals.remove(&proposal_id);
+        self.record_tx_summaries(height, &execution_infos);
+        self.publish_events(events);
         if let Err(mempool_err) =
             self.mempool_client.commit_block(CommitBlockArgs { nonces, tx_hashes }).await
         {
             error!("Failed to commit block to mempool: {}", mempool_err);
             // TODO: Should we rollback the state diff and return an error?
         }
+        Ok(DecisionReachedResponse { already_committed: false, height })
+    }
+
+    /// Pre-confirms a proposal whose transactions were all executed, without committing it. The
+    /// batcher does not compute state roots, so the tentative root is the commitment to the state
+    /// diff of the proposal's executed transactions.
+    pub async fn pre_confirm(&self, input: PreConfirmInput) -> BatcherResult<PreConfirmation> {
+        let proposal_id = input.proposal_id;
+        let commitment = match self.proposal_manager.get_done_proposal_commitment(proposal_id).await
+        {
+            Ok(commitment) => commitment,
+            Err(GetProposalResultError::ProposalDoesNotExist { proposal_id })
+                if !self.known_proposal_ids.contains(&proposal_id) =>
+            {
+                return Err(BatcherError::UnknownProposal { proposal_id });
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let StateDiffCommitment(PoseidonHash(state_diff_hash)) = commitment.state_diff_commitment;
+        Ok(PreConfirmation { proposal_id, tentative_state_root: GlobalRoot(state_diff_hash) })
+    }
+
+    /// Returns a stream of the events matching the given filter, pushed as decisions are reached.
+    /// The stream ends once the batcher is dropped.
+    pub fn subscribe_events(&mut self, filter: EventFilter) -> impl Stream<Item = EventRecord> {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        self.event_subscribers.push((filter, sender));
+        UnboundedReceiverStream::new(receiver)
+    }
+
+    // Sends the committed events to the matching subscribers, dropping the closed subscriptions.
+    fn publish_events(&mut self, events: Vec<EventRecord>) {
+        self.event_subscribers.retain(|(filter, sender)| {
+            !sender.is_closed()
+                && events
+                    .iter()
+                    .filter(|event| filter.matches(event))
+                    .all(|event| sender.send(event.clone()).is_ok())
+        });
+    }
+
+    pub async fn get_proposal_status(
+        &self,
+        input: GetProposalStatusInput,
+    ) -> BatcherResult<GetProposalStatusResponse> {
+        let proposal_id = input.proposal_id;
+        let status = self.proposal_status(proposal_id).await?;
+        let metadata = self
+            .proposal_inputs
+            .get(&proposal_id)
+            .map(|input| input.metadata.clone())
+            .unwrap_or_default();
+        let n_txs = self.proposal_txs.get(&proposal_id).map_or(0, Vec::len);
+        Ok(GetProposalStatusResponse { status, metadata, n_txs })
+    }
+
+    async fn proposal_status(&self, proposal_id: ProposalId) -> BatcherResult<ProposalStatus> {
+        if self.failed_proposal_ids.contains(&proposal_id) {
+            return Ok(ProposalStatus::InvalidProposal);
+        }
+        match self.proposal_manager.get_done_proposal_commitment(proposal_id).await {
+            Ok(proposal_commitment) => Ok(ProposalStatus::Finished(proposal_commitment)),
+            Err(GetProposalResultError::ProposalDoesNotExist { .. })
+                if self.proposals.contains_key(&proposal_id) || self.is_queued(proposal_id) =>
+            {
+                Ok(ProposalStatus::Processing)
+            }
+            Err(GetProposalResultError::ProposalDoesNotExist { proposal_id }) => {
+                Err(self.missing_proposal_error(proposal_id))
+            }
+            Err(err) => {
+                debug!("Proposal {} failed: {}", proposal_id, err);
+                Ok(ProposalStatus::InvalidProposal)
+            }
+        }
+    }
+
+    /// Preloads the hinted classes for the upcoming proposals. Best effort, so this never fails.
+    pub async fn warm_up(&self, hint: WarmUpHint) -> BatcherResult<()> {
+        self.proposal_manager.warm_up(hint.class_hashes).await;
         Ok(())
     }
+
+    pub async fn list_proposals(&self) -> BatcherResult<Vec<(ProposalId, ProposalStatus)>> {
+        let mut proposal_ids: Vec<ProposalId> = self
+            .proposals
+            .keys()
+            .copied()
+            .chain(self.queued_proposals.iter().map(|input| input.proposal_id))
+            .collect();
+        proposal_ids.sort();
+        let mut proposals = Vec::with_capacity(proposal_ids.len());
+        for proposal_id in proposal_ids {
+            let status = self.proposal_status(proposal_id).await?;
+            proposals.push((proposal_id, status));
+        }
+        Ok(proposals)
+    }
+
+    fn is_queued(&self, proposal_id: ProposalId) -> bool {
+        self.queued_proposals.iter().any(|input| input.proposal_id == proposal_id)
+    }
+
+    /// Returns the error for a proposal that is not held by the batcher, distinguishing proposals
+    /// that were never started at the current height from those that are no longer held.
+    fn missing_proposal_error(&self, proposal_id: ProposalId) -> BatcherError {
+        if self.known_proposal_ids.contains(&proposal_id) {
+            BatcherError::ProposalNotFound { proposal_id }
+        } else {
+            BatcherError::UnknownProposal { proposal_id }
+        }
+    }
+
+    /// Returns the summaries of the given committed transactions. Only the transactions of the
+    /// last few committed heights are available (see [N_HEIGHTS_WITH_TX_SUMMARIES]).
+    pub fn summarize_transactions(
+        &self,
+        input: SummarizeTransactionsInput,
+    ) -> BatcherResult<Vec<PortableSummary>> {
+        input
+            .tx_hashes
+            .into_iter()
+            .map(|tx_hash| {
+                self.committed_tx_summaries
+                    .iter()
+                    .find_map(|(_, tx_summaries)| tx_summaries.get(&tx_hash))
+                    .map(ExecutionSummary::to_portable)
+                    .ok_or(BatcherError::TransactionNotFound { tx_hash })
+            })
+            .collect()
+    }
+
+    // Summarizes the transactions committed at the given height, dropping the summaries of the
+    // oldest height beyond [N_HEIGHTS_WITH_TX_SUMMARIES].
+    fn record_tx_summaries(
+        &mut self,
+        height: BlockNumber,
+        execution_infos: &IndexMap<TransactionHash, TransactionExecutionInfo>,
+    ) {
+        let tx_summaries = execution_infos
+            .iter()
+            .map(|(tx_hash, execution_info)| (*tx_hash, execution_info.summarize()))
+            .collect();
+        self.committed_tx_summaries.push_back((height, tx_summaries));
+        if self.committed_tx_summaries.len() > N_HEIGHTS_WITH_TX_SUMMARIES {
+            self.committed_tx_summaries.pop_front();
+        }
+    }
+}
+
+/// The number of most recent committed heights whose transaction summaries are kept in memory.
+pub const N_HEIGHTS_WITH_TX_SUMMARIES: usize = 10;
+
+// The response for a proposal without content yet: an empty chunk when waiting for content,
+// otherwise none.
+fn no_content_yet(sequence: u64, wait: bool) -> Option<GetProposalContentResponse> {
+    wait.then_some(GetProposalContentResponse {
+        content: GetProposalContent::Empty,
+        sequence,
+        is_last: false,
+    })
 }
 
 pub fn create_batcher(config: BatcherConfig, mempool_client: SharedMempoolClient) -> Batcher {
@@ -254,6 +719,9 @@ impl From<BuildProposalError> for BatcherError {
             BuildProposalError::ProposalAlreadyExists { proposal_id } => {
                 BatcherError::ProposalAlreadyExists { proposal_id }
             }
+            BuildProposalError::ResourceExhausted { resource } => {
+                BatcherError::ResourceExhausted { resource }
+            }
         }
     }
 }
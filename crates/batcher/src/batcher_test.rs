@@ -4,29 +4,54 @@ use std::sync::Arc;
 use assert_matches::assert_matches;
 use async_trait::async_trait;
 use blockifier::blockifier::block::BlockNumberHashPair;
+use blockifier::execution::call_info::{CallExecution, CallInfo, OrderedEvent};
+use blockifier::execution::entry_point::CallEntryPoint;
+use blockifier::transaction::objects::TransactionExecutionInfo;
 use futures::future::BoxFuture;
-use futures::FutureExt;
-use mockall::automock;
+use futures::{FutureExt, StreamExt};
+use indexmap::IndexMap;
 use mockall::predicate::eq;
+use mockall::{automock, Sequence};
 use rstest::{fixture, rstest};
 use starknet_api::block::BlockNumber;
-use starknet_api::core::{ContractAddress, Nonce, PatriciaKey, StateDiffCommitment};
+use starknet_api::core::{
+    ClassHash,
+    ContractAddress,
+    GlobalRoot,
+    Nonce,
+    PatriciaKey,
+    StateDiffCommitment,
+};
 use starknet_api::executable_transaction::Transaction;
 use starknet_api::hash::PoseidonHash;
 use starknet_api::state::ThinStateDiff;
-use starknet_api::transaction::TransactionHash;
+use starknet_api::transaction::{EventContent, EventKey, TransactionHash};
 use starknet_api::{felt, nonce, patricia_key};
 use starknet_batcher_types::batcher_types::{
+    BlockResourceBounds,
     BuildProposalInput,
     DecisionReachedInput,
+    DecisionReachedResponse,
+    EventFilter,
+    EventRecord,
     GetProposalContent,
     GetProposalContentInput,
     GetProposalContentResponse,
+    GetProposalStatusInput,
+    PreConfirmInput,
+    PreConfirmation,
+    Priority,
     ProposalCommitment,
     ProposalId,
+    ProposalStatus,
+    RebuildProposalInput,
     StartHeightInput,
+    SummarizeTransactionsInput,
+    WarmUpHint,
 };
+use starknet_batcher_types::communication::{BatcherRequest, BatcherResponse};
 use starknet_batcher_types::errors::BatcherError;
+use starknet_mempool_infra::component_definitions::ComponentRequestHandler;
 use starknet_mempool_types::communication::MockMempoolClient;
 use starknet_mempool_types::mempool_types::CommitBlockArgs;
 
@@ -69,30 +94,781 @@ fn mempool_client() -> MockMempoolClient {
 
 #[rstest]
 #[tokio::test]
-async fn get_stream_content(
+async fn get_stream_content(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+    // Expecting 3 chunks of streamed txs.
+    let expected_streamed_txs = test_txs(0..STREAMING_CHUNK_SIZE * 2 + 1);
+    let txs_to_stream = expected_streamed_txs.clone();
+    let expected_proposal_commitment = ProposalCommitment {
+        state_diff_commitment: StateDiffCommitment(PoseidonHash(felt!(u128::try_from(7).unwrap()))),
+    };
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
+    proposal_manager.expect_wrap_build_block_proposal().return_once(
+        move |_proposal_id,
+              _block_hash,
+              _deadline,
+              _max_txs,
+              _resource_bounds,
+              _seed_txs,
+              tx_sender| {
+            simulate_build_block_proposal(tx_sender, txs_to_stream).boxed()
+        },
+    );
+    proposal_manager
+        .expect_wrap_done_proposal_commitment()
+        .return_once(move |_| async move { Ok(expected_proposal_commitment) }.boxed());
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher
+        .build_proposal(BuildProposalInput {
+            proposal_id: PROPOSAL_ID,
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+        })
+        .await
+        .unwrap();
+
+    let expected_n_chunks = expected_streamed_txs.len().div_ceil(STREAMING_CHUNK_SIZE);
+    let mut aggregated_streamed_txs = Vec::new();
+    for _ in 0..expected_n_chunks {
+        let content = batcher
+            .get_proposal_content(GetProposalContentInput {
+                proposal_id: PROPOSAL_ID,
+                resume_from: None,
+            })
+            .await
+            .unwrap()
+            .content;
+        let mut txs = assert_matches!(content, GetProposalContent::Txs(txs) => txs);
+        assert!(txs.len() <= STREAMING_CHUNK_SIZE, "{} < {}", txs.len(), STREAMING_CHUNK_SIZE);
+        aggregated_streamed_txs.append(&mut txs);
+    }
+    assert_eq!(aggregated_streamed_txs, expected_streamed_txs);
+
+    let commitment = batcher
+        .get_proposal_content(GetProposalContentInput {
+            proposal_id: PROPOSAL_ID,
+            resume_from: None,
+        })
+        .await
+        .unwrap();
+    assert_matches!(
+        commitment,
+        GetProposalContentResponse {
+            content: GetProposalContent::Finished(proposal_commitment),
+            is_last: true,
+            ..
+        } if proposal_commitment == expected_proposal_commitment
+    );
+
+    // The proposal is kept until a decision is reached, so its end is streamed again.
+    let exhausted = batcher
+        .get_proposal_content(GetProposalContentInput {
+            proposal_id: PROPOSAL_ID,
+            resume_from: None,
+        })
+        .await
+        .unwrap();
+    assert_matches!(exhausted.content, GetProposalContent::Finished(_));
+}
+
+#[rstest]
+#[tokio::test]
+async fn build_proposal_with_max_transactions(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+    const MAX_TRANSACTIONS: usize = 2;
+    let expected_streamed_txs = test_txs(0..MAX_TRANSACTIONS);
+    let txs_to_stream = expected_streamed_txs.clone();
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
+    // The proposal manager is expected to stop at the cap.
+    proposal_manager
+        .expect_wrap_build_block_proposal()
+        .withf(|_, _, _, max_txs, _, _, _| *max_txs == Some(MAX_TRANSACTIONS))
+        .return_once(
+            move |_proposal_id,
+                  _block_hash,
+                  _deadline,
+                  _max_txs,
+                  _resource_bounds,
+                  _seed_txs,
+                  tx_sender| {
+                simulate_build_block_proposal(tx_sender, txs_to_stream).boxed()
+            },
+        );
+    proposal_manager
+        .expect_wrap_done_proposal_commitment()
+        .returning(|_| async { Ok(ProposalCommitment::default()) }.boxed());
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher
+        .build_proposal(BuildProposalInput {
+            proposal_id: PROPOSAL_ID,
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: Some(MAX_TRANSACTIONS),
+            resource_bounds: BlockResourceBounds::default(),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+        })
+        .await
+        .unwrap();
+
+    let mut aggregated_streamed_txs = Vec::new();
+    loop {
+        let content = batcher
+            .get_proposal_content(GetProposalContentInput {
+                proposal_id: PROPOSAL_ID,
+                resume_from: None,
+            })
+            .await
+            .unwrap()
+            .content;
+        match content {
+            GetProposalContent::Txs(mut txs) => aggregated_streamed_txs.append(&mut txs),
+            GetProposalContent::Finished(_) => break,
+            GetProposalContent::Empty => {}
+        }
+    }
+    assert_eq!(aggregated_streamed_txs, expected_streamed_txs);
+
+    let response = batcher
+        .get_proposal_status(GetProposalStatusInput { proposal_id: PROPOSAL_ID })
+        .await
+        .unwrap();
+    assert_eq!(response.n_txs, MAX_TRANSACTIONS);
+}
+
+#[rstest]
+#[tokio::test]
+async fn rebuild_proposal_reuses_base_transactions(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const BASE_PROPOSAL_ID: ProposalId = ProposalId(0);
+    const REBUILT_PROPOSAL_ID: ProposalId = ProposalId(1);
+    let base_txs = test_txs(0..STREAMING_CHUNK_SIZE);
+    let expected_seed_txs = base_txs.clone();
+    let base_txs_to_stream = base_txs.clone();
+    let rebuilt_txs =
+        [base_txs.clone(), test_txs(STREAMING_CHUNK_SIZE..STREAMING_CHUNK_SIZE + 1)].concat();
+    let rebuilt_txs_to_stream = rebuilt_txs.clone();
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
+    let mut sequence = Sequence::new();
+    proposal_manager
+        .expect_wrap_build_block_proposal()
+        .withf(|id, _, _, _, _, seed_txs, _| *id == BASE_PROPOSAL_ID && seed_txs.is_empty())
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_once(
+            move |_proposal_id,
+                  _block_hash,
+                  _deadline,
+                  _max_txs,
+                  _resource_bounds,
+                  _seed_txs,
+                  tx_sender| {
+                simulate_build_block_proposal(tx_sender, base_txs_to_stream).boxed()
+            },
+        );
+    proposal_manager
+        .expect_wrap_build_block_proposal()
+        .withf(move |id, _, _, _, _, seed_txs, _| {
+            *id == REBUILT_PROPOSAL_ID && *seed_txs == expected_seed_txs
+        })
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_once(
+            move |_proposal_id,
+                  _block_hash,
+                  _deadline,
+                  _max_txs,
+                  _resource_bounds,
+                  _seed_txs,
+                  tx_sender| {
+                simulate_build_block_proposal(tx_sender, rebuilt_txs_to_stream).boxed()
+            },
+        );
+    proposal_manager
+        .expect_wrap_done_proposal_commitment()
+        .returning(|_| async { Ok(ProposalCommitment::default()) }.boxed());
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher
+        .build_proposal(BuildProposalInput {
+            proposal_id: BASE_PROPOSAL_ID,
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+        })
+        .await
+        .unwrap();
+    assert_eq!(stream_proposal_txs(&mut batcher, BASE_PROPOSAL_ID).await, base_txs);
+
+    batcher
+        .rebuild_proposal(RebuildProposalInput {
+            proposal_id: REBUILT_PROPOSAL_ID,
+            base_proposal_id: BASE_PROPOSAL_ID,
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(2),
+        })
+        .await
+        .unwrap();
+    assert_eq!(stream_proposal_txs(&mut batcher, REBUILT_PROPOSAL_ID).await, rebuilt_txs);
+
+    let unknown_base = batcher
+        .rebuild_proposal(RebuildProposalInput {
+            proposal_id: ProposalId(2),
+            base_proposal_id: ProposalId(7),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(2),
+        })
+        .await;
+    assert_eq!(unknown_base, Err(BatcherError::UnknownProposal { proposal_id: ProposalId(7) }));
+}
+
+// Streams the content of the given proposal until it is finished, returning its transactions.
+async fn stream_proposal_txs(batcher: &mut Batcher, proposal_id: ProposalId) -> Vec<Transaction> {
+    let mut streamed_txs = Vec::new();
+    loop {
+        let content = batcher
+            .get_proposal_content(GetProposalContentInput { proposal_id, resume_from: None })
+            .await
+            .unwrap()
+            .content;
+        match content {
+            GetProposalContent::Txs(mut txs) => streamed_txs.append(&mut txs),
+            GetProposalContent::Finished(_) => return streamed_txs,
+            GetProposalContent::Empty => {}
+        }
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn try_get_stream_content_does_not_wait(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+    let txs_to_stream = test_txs(0..STREAMING_CHUNK_SIZE);
+    let expected_streamed_txs = txs_to_stream.clone();
+    // The proposal emits no transactions until released.
+    let (release_sender, release_receiver) = tokio::sync::oneshot::channel::<()>();
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
+    proposal_manager.expect_wrap_build_block_proposal().return_once(
+        move |_proposal_id,
+              _block_hash,
+              _deadline,
+              _max_txs,
+              _resource_bounds,
+              _seed_txs,
+              tx_sender| {
+            tokio::spawn(async move {
+                release_receiver.await.unwrap();
+                for tx in txs_to_stream {
+                    tx_sender.send(tx).unwrap();
+                }
+            });
+            async { Ok(()) }.boxed()
+        },
+    );
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher
+        .build_proposal(BuildProposalInput {
+            proposal_id: PROPOSAL_ID,
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+        })
+        .await
+        .unwrap();
+
+    let input = GetProposalContentInput { proposal_id: PROPOSAL_ID, resume_from: None };
+    assert_matches!(batcher.try_get_proposal_content(input.clone()).await, Ok(None));
+
+    release_sender.send(()).unwrap();
+    let response = loop {
+        if let Some(response) = batcher.try_get_proposal_content(input.clone()).await.unwrap() {
+            break response;
+        }
+        tokio::task::yield_now().await;
+    };
+    let txs = assert_matches!(response.content, GetProposalContent::Txs(txs) => txs);
+    assert!(expected_streamed_txs.starts_with(&txs));
+}
+
+#[rstest]
+#[tokio::test]
+async fn get_stream_content_resumes_from_sequence(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+    // Expecting 3 chunks of streamed txs.
+    let txs_to_stream = test_txs(0..STREAMING_CHUNK_SIZE * 2 + 1);
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
+    proposal_manager.expect_wrap_build_block_proposal().return_once(
+        move |_proposal_id,
+              _block_hash,
+              _deadline,
+              _max_txs,
+              _resource_bounds,
+              _seed_txs,
+              tx_sender| {
+            simulate_build_block_proposal(tx_sender, txs_to_stream).boxed()
+        },
+    );
+    proposal_manager
+        .expect_wrap_done_proposal_commitment()
+        .returning(|_| async { Ok(ProposalCommitment::default()) }.boxed());
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher
+        .build_proposal(BuildProposalInput {
+            proposal_id: PROPOSAL_ID,
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+        })
+        .await
+        .unwrap();
+
+    let next_input = GetProposalContentInput { proposal_id: PROPOSAL_ID, resume_from: None };
+    let resume_input = |sequence| GetProposalContentInput {
+        proposal_id: PROPOSAL_ID,
+        resume_from: Some(sequence),
+    };
+    let mut streamed_chunks = Vec::new();
+    for _ in 0..3 {
+        let response = batcher.get_proposal_content(next_input.clone()).await.unwrap();
+        streamed_chunks
+            .push(assert_matches!(response.content, GetProposalContent::Txs(txs) => txs));
+    }
+
+    // Chunks 0 and 1 aren't re-sent, only the one resumed from.
+    let resumed = batcher.get_proposal_content(resume_input(2)).await.unwrap();
+    assert_eq!(resumed.sequence, 2);
+    let resumed_txs = assert_matches!(resumed.content, GetProposalContent::Txs(txs) => txs);
+    assert_eq!(resumed_txs, streamed_chunks[2]);
+    assert!(!resumed_txs.iter().any(|tx| streamed_chunks[0].contains(tx)));
+    assert!(!resumed_txs.iter().any(|tx| streamed_chunks[1].contains(tx)));
+
+    // Resuming rewinds the stream: the chunks after the resumed one are streamed again.
+    let resumed = batcher.get_proposal_content(resume_input(1)).await.unwrap();
+    assert_eq!(resumed.sequence, 1);
+    let next = batcher.get_proposal_content(next_input.clone()).await.unwrap();
+    assert_eq!(next.sequence, 2);
+    assert_matches!(next.content, GetProposalContent::Txs(txs) if txs == streamed_chunks[2]);
+
+    // The end of the stream can be resumed as well, e.g., if the last response was lost.
+    let finished = batcher.get_proposal_content(next_input.clone()).await.unwrap();
+    assert_eq!(finished.sequence, 3);
+    assert_matches!(finished.content, GetProposalContent::Finished(_));
+    let resumed = batcher.get_proposal_content(resume_input(3)).await.unwrap();
+    assert_eq!(resumed.sequence, 3);
+    assert_matches!(resumed.content, GetProposalContent::Finished(_));
+
+    let unsent = batcher.get_proposal_content(resume_input(4)).await;
+    assert_matches!(unsent, Err(BatcherError::ResumeFromUnsentChunk { resume_from: 4, .. }));
+}
+
+#[rstest]
+#[tokio::test]
+async fn get_stream_content_of_empty_proposal(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
+    proposal_manager.expect_wrap_build_block_proposal().return_once(
+        move |_proposal_id,
+              _block_hash,
+              _deadline,
+              _max_txs,
+              _resource_bounds,
+              _seed_txs,
+              tx_sender| { simulate_build_block_proposal(tx_sender, vec![]).boxed() },
+    );
+    // The proposal was not marked as done yet.
+    proposal_manager.expect_wrap_done_proposal_commitment().return_once(|proposal_id| {
+        async move { Err(GetProposalResultError::ProposalDoesNotExist { proposal_id }) }.boxed()
+    });
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher
+        .build_proposal(BuildProposalInput {
+            proposal_id: PROPOSAL_ID,
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+        })
+        .await
+        .unwrap();
+
+    let response = batcher
+        .get_proposal_content(GetProposalContentInput {
+            proposal_id: PROPOSAL_ID,
+            resume_from: None,
+        })
+        .await
+        .unwrap();
+    assert!(response.is_empty());
+}
+
+#[rstest]
+#[tokio::test]
+async fn build_proposal_resource_exhausted(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
+    proposal_manager.expect_wrap_build_block_proposal().return_once(|_, _, _, _, _, _, _| {
+        async { Err(BuildProposalError::ResourceExhausted { resource: "memory".to_string() }) }
+            .boxed()
+    });
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    let err = batcher
+        .build_proposal(BuildProposalInput {
+            proposal_id: ProposalId(0),
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+        })
+        .await
+        .unwrap_err();
+
+    assert_eq!(err, BatcherError::ResourceExhausted { resource: "memory".to_string() });
+    assert!(!err.is_retryable());
+    let server_busy = BatcherError::ServerBusy {
+        active_proposal_id: ProposalId(0),
+        new_proposal_id: ProposalId(1),
+    };
+    assert!(!server_busy.is_retryable());
+}
+
+#[rstest]
+#[tokio::test]
+async fn proposal_status_echoes_metadata(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+    let metadata = b"scheduler:7".to_vec();
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
+    proposal_manager
+        .expect_wrap_build_block_proposal()
+        .return_once(|_, _, _, _, _, _, _| async { Ok(()) }.boxed());
+    proposal_manager.expect_wrap_done_proposal_commitment().return_once(|proposal_id| {
+        async move { Err(GetProposalResultError::ProposalDoesNotExist { proposal_id }) }.boxed()
+    });
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher
+        .build_proposal(BuildProposalInput {
+            proposal_id: PROPOSAL_ID,
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: metadata.clone(),
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+        })
+        .await
+        .unwrap();
+
+    let response = batcher
+        .get_proposal_status(GetProposalStatusInput { proposal_id: PROPOSAL_ID })
+        .await
+        .unwrap();
+    assert_matches!(response.status, ProposalStatus::Processing);
+    assert_eq!(response.metadata, metadata);
+}
+
+#[rstest]
+#[tokio::test]
+async fn queued_proposals_start_by_priority(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const ACTIVE_PROPOSAL_ID: ProposalId = ProposalId(0);
+    const LOW_PROPOSAL_ID: ProposalId = ProposalId(1);
+    const HIGH_PROPOSAL_ID: ProposalId = ProposalId(2);
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
+    let mut sequence = Sequence::new();
+    // Both requests arrive while another proposal is being built.
+    for proposal_id in [LOW_PROPOSAL_ID, HIGH_PROPOSAL_ID] {
+        proposal_manager
+            .expect_wrap_build_block_proposal()
+            .withf(move |id, _, _, _, _, _, _| *id == proposal_id)
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(move |_, _, _, _, _, _, _| {
+                async move {
+                    Err(BuildProposalError::AlreadyGeneratingProposal {
+                        current_generating_proposal_id: ACTIVE_PROPOSAL_ID,
+                        new_proposal_id: proposal_id,
+                    })
+                }
+                .boxed()
+            });
+    }
+    proposal_manager
+        .expect_wrap_build_block_proposal()
+        .withf(|id, _, _, _, _, _, _| *id == HIGH_PROPOSAL_ID)
+        .times(1)
+        .in_sequence(&mut sequence)
+        .returning(|_, _, _, _, _, _, _| async { Ok(()) }.boxed());
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    for (proposal_id, priority) in
+        [(LOW_PROPOSAL_ID, Priority::Low), (HIGH_PROPOSAL_ID, Priority::High)]
+    {
+        batcher
+            .build_proposal(BuildProposalInput {
+                proposal_id,
+                retrospective_block_hash: None,
+                priority,
+                metadata: vec![],
+                max_transactions: None,
+                resource_bounds: BlockResourceBounds::default(),
+                deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+            })
+            .await
+            .unwrap();
+    }
+
+    let queued = batcher
+        .try_get_proposal_content(GetProposalContentInput {
+            proposal_id: LOW_PROPOSAL_ID,
+            resume_from: None,
+        })
+        .await
+        .unwrap();
+    assert!(queued.is_none());
+
+    assert_eq!(batcher.start_next_queued_proposal().await.unwrap(), Some(HIGH_PROPOSAL_ID));
+}
+
+#[rstest]
+#[tokio::test]
+async fn get_proposal_content_starts_queued_proposal_once_free(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const ACTIVE_PROPOSAL_ID: ProposalId = ProposalId(0);
+    const QUEUED_PROPOSAL_ID: ProposalId = ProposalId(1);
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
+    let mut sequence = Sequence::new();
+    // The request is queued, the batcher is still busy on the first retry, and free on the second.
+    proposal_manager
+        .expect_wrap_build_block_proposal()
+        .times(2)
+        .in_sequence(&mut sequence)
+        .returning(|_, _, _, _, _, _, _| {
+            async {
+                Err(BuildProposalError::AlreadyGeneratingProposal {
+                    current_generating_proposal_id: ACTIVE_PROPOSAL_ID,
+                    new_proposal_id: QUEUED_PROPOSAL_ID,
+                })
+            }
+            .boxed()
+        });
+    proposal_manager
+        .expect_wrap_build_block_proposal()
+        .times(1)
+        .in_sequence(&mut sequence)
+        .returning(|_, _, _, _, _, _, _| async { Ok(()) }.boxed());
+    proposal_manager
+        .expect_wrap_done_proposal_commitment()
+        .return_once(|_| async { Ok(ProposalCommitment::default()) }.boxed());
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher
+        .build_proposal(BuildProposalInput {
+            proposal_id: QUEUED_PROPOSAL_ID,
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+        })
+        .await
+        .unwrap();
+
+    // While the batcher is busy, the proposal has no content yet, without blocking.
+    let input = GetProposalContentInput { proposal_id: QUEUED_PROPOSAL_ID, resume_from: None };
+    let response = batcher.get_proposal_content(input.clone()).await.unwrap();
+    assert!(response.is_empty());
+
+    let response = batcher.get_proposal_content(input).await.unwrap();
+    assert_matches!(response.content, GetProposalContent::Finished(_));
+}
+
+#[rstest]
+#[tokio::test]
+async fn expired_queued_proposal_fails(
     batcher_config: BatcherConfig,
     storage_reader: MockBatcherStorageReaderTrait,
     storage_writer: MockBatcherStorageWriterTrait,
     mempool_client: MockMempoolClient,
 ) {
-    const PROPOSAL_ID: ProposalId = ProposalId(0);
-    // Expecting 3 chunks of streamed txs.
-    let expected_streamed_txs = test_txs(0..STREAMING_CHUNK_SIZE * 2 + 1);
-    let txs_to_stream = expected_streamed_txs.clone();
-    let expected_proposal_commitment = ProposalCommitment {
-        state_diff_commitment: StateDiffCommitment(PoseidonHash(felt!(u128::try_from(7).unwrap()))),
-    };
+    const ACTIVE_PROPOSAL_ID: ProposalId = ProposalId(0);
+    const QUEUED_PROPOSAL_ID: ProposalId = ProposalId(1);
 
     let mut proposal_manager = MockProposalManagerTraitWrapper::new();
     proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
-    proposal_manager.expect_wrap_build_block_proposal().return_once(
-        move |_proposal_id, _block_hash, _deadline, tx_sender| {
-            simulate_build_block_proposal(tx_sender, txs_to_stream).boxed()
+    proposal_manager.expect_wrap_build_block_proposal().times(1).returning(
+        |_, _, _, _, _, _, _| {
+            async {
+                Err(BuildProposalError::AlreadyGeneratingProposal {
+                    current_generating_proposal_id: ACTIVE_PROPOSAL_ID,
+                    new_proposal_id: QUEUED_PROPOSAL_ID,
+                })
+            }
+            .boxed()
         },
     );
-    proposal_manager
-        .expect_wrap_done_proposal_commitment()
-        .return_once(move |_| async move { Ok(expected_proposal_commitment) }.boxed());
 
     let mut batcher = Batcher::new(
         batcher_config,
@@ -101,45 +877,116 @@ async fn get_stream_content(
         Arc::new(mempool_client),
         Box::new(proposal_manager),
     );
-
     batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
     batcher
         .build_proposal(BuildProposalInput {
-            proposal_id: PROPOSAL_ID,
+            proposal_id: QUEUED_PROPOSAL_ID,
             retrospective_block_hash: None,
-            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+            deadline: chrono::Utc::now() + chrono::Duration::milliseconds(50),
         })
         .await
         .unwrap();
+    // Let the deadline pass while the request is queued.
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
 
-    let expected_n_chunks = expected_streamed_txs.len().div_ceil(STREAMING_CHUNK_SIZE);
-    let mut aggregated_streamed_txs = Vec::new();
-    for _ in 0..expected_n_chunks {
-        let content = batcher
-            .get_proposal_content(GetProposalContentInput { proposal_id: PROPOSAL_ID })
+    assert_matches!(
+        batcher.start_next_queued_proposal().await,
+        Err(BatcherError::TimeToDeadlineError { .. })
+    );
+    let response = batcher
+        .get_proposal_status(GetProposalStatusInput { proposal_id: QUEUED_PROPOSAL_ID })
+        .await
+        .unwrap();
+    assert_matches!(response.status, ProposalStatus::InvalidProposal);
+    assert_eq!(
+        batcher
+            .get_proposal_content(GetProposalContentInput {
+                proposal_id: QUEUED_PROPOSAL_ID,
+                resume_from: None,
+            })
             .await
-            .unwrap()
-            .content;
-        let mut txs = assert_matches!(content, GetProposalContent::Txs(txs) => txs);
-        assert!(txs.len() <= STREAMING_CHUNK_SIZE, "{} < {}", txs.len(), STREAMING_CHUNK_SIZE);
-        aggregated_streamed_txs.append(&mut txs);
+            .unwrap_err(),
+        BatcherError::ProposalFailed
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn list_proposals(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const DONE_PROPOSAL_ID: ProposalId = ProposalId(0);
+    const IN_PROGRESS_PROPOSAL_ID: ProposalId = ProposalId(1);
+    const QUEUED_PROPOSAL_ID: ProposalId = ProposalId(2);
+    let expected_proposal_commitment = ProposalCommitment::default();
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
+    proposal_manager.expect_wrap_build_block_proposal().times(3).returning(
+        |proposal_id, _block_hash, _deadline, _max_txs, _resource_bounds, _seed_txs, tx_sender| {
+            if proposal_id == QUEUED_PROPOSAL_ID {
+                return async move {
+                    Err(BuildProposalError::AlreadyGeneratingProposal {
+                        current_generating_proposal_id: IN_PROGRESS_PROPOSAL_ID,
+                        new_proposal_id: proposal_id,
+                    })
+                }
+                .boxed();
+            }
+            simulate_build_block_proposal(tx_sender, vec![]).boxed()
+        },
+    );
+    proposal_manager.expect_wrap_done_proposal_commitment().returning(move |proposal_id| {
+        async move {
+            if proposal_id == DONE_PROPOSAL_ID {
+                Ok(expected_proposal_commitment)
+            } else {
+                Err(GetProposalResultError::ProposalDoesNotExist { proposal_id })
+            }
+        }
+        .boxed()
+    });
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    for proposal_id in [IN_PROGRESS_PROPOSAL_ID, DONE_PROPOSAL_ID, QUEUED_PROPOSAL_ID] {
+        batcher
+            .build_proposal(BuildProposalInput {
+                proposal_id,
+                retrospective_block_hash: None,
+                priority: Priority::Normal,
+                metadata: vec![],
+                max_transactions: None,
+                resource_bounds: BlockResourceBounds::default(),
+                deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+            })
+            .await
+            .unwrap();
     }
-    assert_eq!(aggregated_streamed_txs, expected_streamed_txs);
 
-    let commitment = batcher
-        .get_proposal_content(GetProposalContentInput { proposal_id: PROPOSAL_ID })
-        .await
-        .unwrap();
+    let proposals = batcher.list_proposals().await.unwrap();
     assert_matches!(
-        commitment,
-        GetProposalContentResponse {
-            content: GetProposalContent::Finished(proposal_commitment)
-        } if proposal_commitment == expected_proposal_commitment
+        proposals.as_slice(),
+        [
+            (DONE_PROPOSAL_ID, ProposalStatus::Finished(proposal_commitment)),
+            (IN_PROGRESS_PROPOSAL_ID, ProposalStatus::Processing),
+            (QUEUED_PROPOSAL_ID, ProposalStatus::Processing),
+        ] if *proposal_commitment == expected_proposal_commitment
     );
-
-    let exhausted =
-        batcher.get_proposal_content(GetProposalContentInput { proposal_id: PROPOSAL_ID }).await;
-    assert_matches!(exhausted, Err(BatcherError::ProposalNotFound { .. }));
 }
 
 #[rstest]
@@ -168,6 +1015,8 @@ async fn decision_reached(
                     commitment: expected_proposal_commitment,
                     tx_hashes: tx_hashes_clone,
                     nonces: nonces_clone,
+                    execution_infos: IndexMap::new(),
+                    events: Vec::new(),
                 })
             }
             .boxed()
@@ -176,11 +1025,13 @@ async fn decision_reached(
     mempool_client
         .expect_commit_block()
         .with(eq(CommitBlockArgs { nonces, tx_hashes }))
+        .times(1)
         .returning(|_| Ok(()));
 
     storage_writer
         .expect_commit_proposal()
         .with(eq(INITIAL_HEIGHT), eq(expected_state_diff))
+        .times(1)
         .returning(|_, _| Ok(()));
 
     let mut batcher = Batcher::new(
@@ -190,7 +1041,20 @@ async fn decision_reached(
         Arc::new(mempool_client),
         Box::new(proposal_manager),
     );
-    batcher.decision_reached(DecisionReachedInput { proposal_id: ProposalId(0) }).await.unwrap();
+    let response =
+        batcher.decision_reached(DecisionReachedInput { proposal_id: PROPOSAL_ID }).await.unwrap();
+    assert_eq!(
+        response,
+        DecisionReachedResponse { already_committed: false, height: INITIAL_HEIGHT }
+    );
+
+    // A repeated decision is not committed again.
+    let response =
+        batcher.decision_reached(DecisionReachedInput { proposal_id: PROPOSAL_ID }).await.unwrap();
+    assert_eq!(
+        response,
+        DecisionReachedResponse { already_committed: true, height: INITIAL_HEIGHT }
+    );
 }
 
 #[rstest]
@@ -204,6 +1068,59 @@ async fn decision_reached_no_done_proposal(
     const PROPOSAL_ID: ProposalId = ProposalId(0);
     let expected_error = BatcherError::DoneProposalNotFound { proposal_id: PROPOSAL_ID };
 
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_start_height().return_once(|_| async { Ok(()) }.boxed());
+    proposal_manager.expect_wrap_build_block_proposal().return_once(
+        move |_proposal_id,
+              _block_hash,
+              _deadline,
+              _max_txs,
+              _resource_bounds,
+              _seed_txs,
+              tx_sender| { simulate_build_block_proposal(tx_sender, vec![]).boxed() },
+    );
+    proposal_manager.expect_wrap_take_proposal_result().with(eq(PROPOSAL_ID)).return_once(
+        |proposal_id| {
+            async move { Err(GetProposalResultError::ProposalDoesNotExist { proposal_id }) }.boxed()
+        },
+    );
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher
+        .build_proposal(BuildProposalInput {
+            proposal_id: PROPOSAL_ID,
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+        })
+        .await
+        .unwrap();
+    let decision_reached_result =
+        batcher.decision_reached(DecisionReachedInput { proposal_id: PROPOSAL_ID }).await;
+    assert_eq!(decision_reached_result, Err(expected_error));
+}
+
+#[rstest]
+#[tokio::test]
+async fn decision_reached_unknown_proposal(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+    let expected_error = BatcherError::UnknownProposal { proposal_id: PROPOSAL_ID };
+
     let mut proposal_manager = MockProposalManagerTraitWrapper::new();
     proposal_manager.expect_wrap_take_proposal_result().with(eq(PROPOSAL_ID)).return_once(
         |proposal_id| {
@@ -223,6 +1140,233 @@ async fn decision_reached_no_done_proposal(
     assert_eq!(decision_reached_result, Err(expected_error));
 }
 
+#[rstest]
+#[tokio::test]
+async fn summarize_committed_transactions(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    mut storage_writer: MockBatcherStorageWriterTrait,
+    mut mempool_client: MockMempoolClient,
+) {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+    let tx_hashes: Vec<TransactionHash> = test_tx_hashes(0..2).into_iter().collect();
+    let execution_info = |n_events| TransactionExecutionInfo {
+        execute_call_info: Some(CallInfo {
+            call: CallEntryPoint { class_hash: Some(ClassHash::default()), ..Default::default() },
+            execution: CallExecution {
+                events: (0..n_events).map(|_| OrderedEvent::default()).collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let expected_summaries: Vec<_> =
+        (1..=2).map(|n_events| execution_info(n_events).summarize().to_portable()).collect();
+    let execution_infos: IndexMap<TransactionHash, TransactionExecutionInfo> =
+        tx_hashes.iter().copied().zip((1..=2).map(execution_info)).collect();
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_take_proposal_result().with(eq(PROPOSAL_ID)).return_once(
+        move |_| {
+            async move {
+                Ok(ProposalOutput {
+                    state_diff: ThinStateDiff::default(),
+                    commitment: ProposalCommitment::default(),
+                    tx_hashes: HashSet::from_iter(execution_infos.keys().copied()),
+                    nonces: HashMap::new(),
+                    execution_infos,
+                    events: Vec::new(),
+                })
+            }
+            .boxed()
+        },
+    );
+    mempool_client.expect_commit_block().returning(|_| Ok(()));
+    storage_writer.expect_commit_proposal().returning(|_, _| Ok(()));
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+    batcher.decision_reached(DecisionReachedInput { proposal_id: PROPOSAL_ID }).await.unwrap();
+
+    let response = batcher
+        .handle_request(BatcherRequest::SummarizeTransactions(SummarizeTransactionsInput {
+            tx_hashes,
+        }))
+        .await;
+    let summaries = assert_matches!(
+        response,
+        BatcherResponse::SummarizeTransactions(Ok(summaries)) => summaries
+    );
+    assert_eq!(summaries, expected_summaries);
+}
+
+#[rstest]
+#[tokio::test]
+async fn warm_up_reaches_proposal_manager(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    let class_hashes = vec![ClassHash(felt!("0x1")), ClassHash(felt!("0x2"))];
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager
+        .expect_wrap_warm_up()
+        .with(eq(class_hashes.clone()))
+        .times(1)
+        .return_once(|_| async {}.boxed());
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+
+    let response =
+        batcher.handle_request(BatcherRequest::WarmUp(WarmUpHint { class_hashes })).await;
+    assert_matches!(response, BatcherResponse::WarmUp(Ok(())));
+}
+
+#[rstest]
+#[tokio::test]
+async fn pre_confirm_carries_state_diff_commitment(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+    let state_diff_hash = felt!("0x1234");
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_done_proposal_commitment().with(eq(PROPOSAL_ID)).return_once(
+        move |_| {
+            async move {
+                Ok(ProposalCommitment {
+                    state_diff_commitment: StateDiffCommitment(PoseidonHash(state_diff_hash)),
+                })
+            }
+            .boxed()
+        },
+    );
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+
+    let response = batcher
+        .handle_request(BatcherRequest::PreConfirm(PreConfirmInput { proposal_id: PROPOSAL_ID }))
+        .await;
+    let pre_confirmation = assert_matches!(
+        response,
+        BatcherResponse::PreConfirm(Ok(pre_confirmation)) => pre_confirmation
+    );
+    let expected_pre_confirmation = PreConfirmation {
+        proposal_id: PROPOSAL_ID,
+        tentative_state_root: GlobalRoot(state_diff_hash),
+    };
+    assert_eq!(pre_confirmation, expected_pre_confirmation);
+}
+
+#[rstest]
+#[tokio::test]
+async fn pre_confirm_unknown_proposal(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    storage_writer: MockBatcherStorageWriterTrait,
+    mempool_client: MockMempoolClient,
+) {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_done_proposal_commitment().return_once(|proposal_id| {
+        async move { Err(GetProposalResultError::ProposalDoesNotExist { proposal_id }) }.boxed()
+    });
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+
+    let response = batcher
+        .handle_request(BatcherRequest::PreConfirm(PreConfirmInput { proposal_id: PROPOSAL_ID }))
+        .await;
+    assert_matches!(
+        response,
+        BatcherResponse::PreConfirm(Err(BatcherError::UnknownProposal { proposal_id }))
+        if proposal_id == PROPOSAL_ID
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn subscribe_events(
+    batcher_config: BatcherConfig,
+    storage_reader: MockBatcherStorageReaderTrait,
+    mut storage_writer: MockBatcherStorageWriterTrait,
+    mut mempool_client: MockMempoolClient,
+) {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+    let subscribed_address = ContractAddress(patricia_key!("0x1"));
+    let event_record = |from_address: ContractAddress| EventRecord {
+        tx_hash: TransactionHash(felt!("0x1")),
+        from_address,
+        content: EventContent { keys: vec![EventKey(felt!("0x2"))], ..Default::default() },
+    };
+    let matching_event = event_record(subscribed_address);
+    let events = vec![matching_event.clone(), event_record(ContractAddress(patricia_key!("0x2")))];
+
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_take_proposal_result().with(eq(PROPOSAL_ID)).return_once(
+        move |_| {
+            async move {
+                Ok(ProposalOutput {
+                    state_diff: ThinStateDiff::default(),
+                    commitment: ProposalCommitment::default(),
+                    tx_hashes: HashSet::new(),
+                    nonces: HashMap::new(),
+                    execution_infos: IndexMap::new(),
+                    events,
+                })
+            }
+            .boxed()
+        },
+    );
+    mempool_client.expect_commit_block().returning(|_| Ok(()));
+    storage_writer.expect_commit_proposal().returning(|_, _| Ok(()));
+
+    let mut batcher = Batcher::new(
+        batcher_config,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(mempool_client),
+        Box::new(proposal_manager),
+    );
+    let mut event_stream = batcher.subscribe_events(EventFilter {
+        contract_address: Some(subscribed_address),
+        first_key: Some(EventKey(felt!("0x2"))),
+    });
+    batcher.decision_reached(DecisionReachedInput { proposal_id: PROPOSAL_ID }).await.unwrap();
+    drop(batcher);
+
+    assert_eq!(event_stream.next().await, Some(matching_event));
+    assert_eq!(event_stream.next().await, None);
+}
+
 async fn simulate_build_block_proposal(
     tx_sender: tokio::sync::mpsc::UnboundedSender<Transaction>,
     txs: Vec<Transaction>,
@@ -248,6 +1392,9 @@ trait ProposalManagerTraitWrapper: Send + Sync {
         proposal_id: ProposalId,
         retrospective_block_hash: Option<BlockNumberHashPair>,
         deadline: tokio::time::Instant,
+        max_txs: Option<usize>,
+        resource_bounds: BlockResourceBounds,
+        seed_txs: Vec<Transaction>,
         output_content_sender: tokio::sync::mpsc::UnboundedSender<Transaction>,
     ) -> BoxFuture<'_, Result<(), BuildProposalError>>;
 
@@ -260,6 +1407,8 @@ trait ProposalManagerTraitWrapper: Send + Sync {
         &self,
         proposal_id: ProposalId,
     ) -> BoxFuture<'_, ProposalResult<ProposalCommitment>>;
+
+    fn wrap_warm_up(&self, class_hashes: Vec<ClassHash>) -> BoxFuture<'_, ()>;
 }
 
 #[async_trait]
@@ -273,12 +1422,18 @@ impl<T: ProposalManagerTraitWrapper> ProposalManagerTrait for T {
         proposal_id: ProposalId,
         retrospective_block_hash: Option<BlockNumberHashPair>,
         deadline: tokio::time::Instant,
+        max_txs: Option<usize>,
+        resource_bounds: BlockResourceBounds,
+        seed_txs: Vec<Transaction>,
         output_content_sender: tokio::sync::mpsc::UnboundedSender<Transaction>,
     ) -> Result<(), BuildProposalError> {
         self.wrap_build_block_proposal(
             proposal_id,
             retrospective_block_hash,
             deadline,
+            max_txs,
+            resource_bounds,
+            seed_txs,
             output_content_sender,
         )
         .await
@@ -297,6 +1452,10 @@ impl<T: ProposalManagerTraitWrapper> ProposalManagerTrait for T {
     ) -> ProposalResult<ProposalCommitment> {
         self.wrap_done_proposal_commitment(proposal_id).await
     }
+
+    async fn warm_up(&self, class_hashes: Vec<ClassHash>) {
+        self.wrap_warm_up(class_hashes).await
+    }
 }
 
 fn test_tx_hashes(range: std::ops::Range<u128>) -> HashSet<TransactionHash> {
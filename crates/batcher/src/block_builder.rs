@@ -28,9 +28,10 @@ use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_storage::StorageReader;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockNumber, BlockTimestamp, NonzeroGasPrice};
-use starknet_api::core::ContractAddress;
+use starknet_api::core::{ClassHash, ContractAddress};
 use starknet_api::executable_transaction::Transaction;
 use starknet_api::transaction::TransactionHash;
+use starknet_batcher_types::batcher_types::BlockResourceBounds;
 use thiserror::Error;
 use tokio::sync::Mutex;
 use tokio::{pin, time};
@@ -44,11 +45,17 @@ pub struct BlockBuilder {
     // TODO(Yael 14/10/2024): make the executor thread safe and delete this mutex.
     executor: Mutex<Box<dyn TransactionExecutorTrait>>,
     tx_chunk_size: usize,
+    // The maximal number of transactions to include in the block, if limited.
+    max_txs: Option<usize>,
 }
 
 impl BlockBuilder {
-    pub fn new(executor: Box<dyn TransactionExecutorTrait>, tx_chunk_size: usize) -> Self {
-        Self { executor: Mutex::new(executor), tx_chunk_size }
+    pub fn new(
+        executor: Box<dyn TransactionExecutorTrait>,
+        tx_chunk_size: usize,
+        max_txs: Option<usize>,
+    ) -> Self {
+        Self { executor: Mutex::new(executor), tx_chunk_size, max_txs }
     }
 }
 
@@ -151,7 +158,13 @@ impl BlockBuilderTrait for BlockBuilder {
                     debug!("No further transactions to execute, timeout was reached.");
                     break;
                 }
-                Ok(Some(tx_chunk)) => tx_chunk,
+                Ok(Some(mut tx_chunk)) => {
+                    if let Some(max_txs) = self.max_txs {
+                        // TODO: Notify the mempool about the transactions left out of the block.
+                        tx_chunk.truncate(max_txs - execution_infos.len());
+                    }
+                    tx_chunk
+                }
                 Ok(None) => return Err(BlockBuilderError::InputStreamTerminated),
             };
             let mut executor_input_chunk = vec![];
@@ -167,6 +180,10 @@ impl BlockBuilderTrait for BlockBuilder {
                 &output_content_sender,
             )
             .await?;
+            if self.max_txs.is_some_and(|max_txs| execution_infos.len() >= max_txs) {
+                info!("Block reached the maximal number of transactions.");
+                should_close_block = true;
+            }
         }
         let (commitment_state_diff, visited_segments_mapping, bouncer_weights) =
             self.executor.lock().await.close_block()?;
@@ -229,14 +246,21 @@ pub trait BlockBuilderTrait: Send {
     ) -> BlockBuilderResult<BlockExecutionArtifacts>;
 }
 
-/// The BlockBuilderFactoryTrait is responsible for creating a new block builder.
+/// The BlockBuilderFactoryTrait is responsible for creating a new block builder, including at
+/// most max_txs transactions in the block, if given, within the given resource bounds.
 #[cfg_attr(test, automock)]
 pub trait BlockBuilderFactoryTrait {
     fn create_block_builder(
         &self,
         height: BlockNumber,
         retrospective_block_hash: Option<BlockNumberHashPair>,
+        max_txs: Option<usize>,
+        resource_bounds: BlockResourceBounds,
     ) -> BlockBuilderResult<Box<dyn BlockBuilderTrait>>;
+
+    /// Loads the given classes, as of the given height, into the class cache shared by the block
+    /// builders. Classes that fail to load are skipped.
+    fn warm_up(&self, height: BlockNumber, class_hashes: &[ClassHash]);
 }
 
 pub struct BlockBuilderFactory {
@@ -250,6 +274,7 @@ impl BlockBuilderFactory {
         &self,
         height: BlockNumber,
         retrospective_block_hash: Option<BlockNumberHashPair>,
+        resource_bounds: BlockResourceBounds,
     ) -> BlockBuilderResult<TransactionExecutor<PapyrusReader>> {
         let block_builder_config = self.block_builder_config.clone();
         let next_block_info = BlockInfo {
@@ -269,7 +294,7 @@ impl BlockBuilderFactory {
             VersionedConstants::get_versioned_constants(
                 block_builder_config.versioned_constants_overrides,
             ),
-            block_builder_config.bouncer_config,
+            bounded_bouncer_config(block_builder_config.bouncer_config, resource_bounds),
         );
 
         // TODO(Yael: 8/9/2024) Need to reconsider which StateReader to use. the papyrus execution
@@ -295,15 +320,55 @@ impl BlockBuilderFactory {
     }
 }
 
+/// Caps the block capacity of the given bouncer config by the given resource bounds, so that the
+/// executor closes the block once a bound is reached.
+pub(crate) fn bounded_bouncer_config(
+    bouncer_config: BouncerConfig,
+    resource_bounds: BlockResourceBounds,
+) -> BouncerConfig {
+    let BlockResourceBounds { max_steps, max_gas } = resource_bounds;
+    let mut block_max_capacity = bouncer_config.block_max_capacity;
+    if let Some(max_steps) = max_steps {
+        block_max_capacity.n_steps = block_max_capacity.n_steps.min(max_steps);
+    }
+    if let Some(max_gas) = max_gas {
+        block_max_capacity.gas = block_max_capacity.gas.min(max_gas);
+    }
+    BouncerConfig { block_max_capacity }
+}
+
 impl BlockBuilderFactoryTrait for BlockBuilderFactory {
     fn create_block_builder(
         &self,
         height: BlockNumber,
         retrospective_block_hash: Option<BlockNumberHashPair>,
+        max_txs: Option<usize>,
+        resource_bounds: BlockResourceBounds,
     ) -> BlockBuilderResult<Box<dyn BlockBuilderTrait>> {
-        let executor =
-            self.preprocess_and_create_transaction_executor(height, retrospective_block_hash)?;
-        Ok(Box::new(BlockBuilder::new(Box::new(executor), self.block_builder_config.tx_chunk_size)))
+        let executor = self.preprocess_and_create_transaction_executor(
+            height,
+            retrospective_block_hash,
+            resource_bounds,
+        )?;
+        Ok(Box::new(BlockBuilder::new(
+            Box::new(executor),
+            self.block_builder_config.tx_chunk_size,
+            max_txs,
+        )))
+    }
+
+    fn warm_up(&self, height: BlockNumber, class_hashes: &[ClassHash]) {
+        let state_reader = PapyrusReader::new(
+            self.storage_reader.clone(),
+            height,
+            self.global_class_hash_to_class.clone(),
+        );
+        for class_hash in class_hashes {
+            // A class read through the reader is stored in the global cache.
+            if let Err(err) = state_reader.get_compiled_contract_class(*class_hash) {
+                debug!("Skipping warm-up of class {class_hash}: {err}");
+            }
+        }
     }
 }
 
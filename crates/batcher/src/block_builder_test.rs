@@ -1,28 +1,37 @@
+use blockifier::blockifier::config::TransactionExecutorConfig;
 use blockifier::blockifier::transaction_executor::{
+    TransactionExecutor,
     TransactionExecutorError,
     TransactionExecutorResult,
 };
-use blockifier::bouncer::BouncerWeights;
+use blockifier::bouncer::{BouncerConfig, BouncerWeights};
+use blockifier::context::BlockContext;
+use blockifier::test_utils::CairoVersion;
 use blockifier::transaction::objects::TransactionExecutionInfo;
+use blockifier::transaction::test_utils::{create_test_init_data, emit_n_events_tx, TestInitData};
 use blockifier::transaction::transaction_execution::Transaction as BlockifierTransaction;
 use indexmap::IndexMap;
 use rstest::{fixture, rstest};
 use starknet_api::executable_transaction::Transaction;
 use starknet_api::felt;
 use starknet_api::transaction::TransactionHash;
+use starknet_batcher_types::batcher_types::BlockResourceBounds;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use tokio_stream::wrappers::ReceiverStream;
 
 use crate::block_builder::{
+    bounded_bouncer_config,
     BlockBuilder,
     BlockBuilderTrait,
     BlockExecutionArtifacts,
     MockTransactionExecutorTrait,
+    TransactionExecutorTrait,
 };
 use crate::test_utils::test_txs;
 
 const TEST_DEADLINE_SECS: u64 = 1;
 const TEST_CHANNEL_SIZE: usize = 50;
+const N_BOUNDED_BLOCK_TXS: usize = 4;
 
 #[fixture]
 fn input_channel() -> (mpsc::Sender<Transaction>, ReceiverStream<Transaction>) {
@@ -71,7 +80,7 @@ async fn test_build_block(
 
     // Build the block.
     let mut block_builder =
-        BlockBuilder::new(Box::new(mock_transaction_executor), execution_chunk_size);
+        BlockBuilder::new(Box::new(mock_transaction_executor), execution_chunk_size, None);
     let deadline =
         tokio::time::Instant::now() + tokio::time::Duration::from_secs(TEST_DEADLINE_SECS);
 
@@ -100,6 +109,98 @@ async fn test_build_block(
     assert_eq!(result_block_artifacts, expected_block_artifacts);
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_build_block_with_max_txs(
+    input_channel: (mpsc::Sender<Transaction>, ReceiverStream<Transaction>),
+    output_channel: (UnboundedSender<Transaction>, UnboundedReceiver<Transaction>),
+) {
+    const MAX_TXS: usize = 2;
+    let (input_sender, input_receiver) = input_channel;
+    let (output_sender, mut output_stream_receiver) = output_channel;
+    let input_txs = test_txs(0..5);
+
+    // Only the transactions up to the cap are executed, all in the first chunk.
+    let (mock_transaction_executor, expected_block_artifacts) =
+        set_transaction_executor_expectations(&input_txs[..MAX_TXS], MAX_TXS, 5, false, None);
+    let mut block_builder =
+        BlockBuilder::new(Box::new(mock_transaction_executor), 5, Some(MAX_TXS));
+    let deadline =
+        tokio::time::Instant::now() + tokio::time::Duration::from_secs(TEST_DEADLINE_SECS);
+
+    for tx in input_txs.iter() {
+        input_sender.send(tx.clone()).await.unwrap();
+    }
+    let result_block_artifacts =
+        block_builder.build_block(deadline, input_receiver, output_sender).await.unwrap();
+
+    let mut output_txs = vec![];
+    output_stream_receiver.recv_many(&mut output_txs, TEST_CHANNEL_SIZE).await;
+    assert_eq!(output_txs, input_txs[..MAX_TXS]);
+    assert_eq!(result_block_artifacts, expected_block_artifacts);
+}
+
+#[test]
+fn test_bounded_bouncer_config() {
+    let bouncer_config = BouncerConfig {
+        block_max_capacity: BouncerWeights { n_steps: 100, gas: 100, ..BouncerWeights::max() },
+    };
+    let resource_bounds = BlockResourceBounds { max_steps: Some(10), max_gas: Some(1000) };
+
+    // Bounds only lower the configured capacity.
+    let bounded_config = bounded_bouncer_config(bouncer_config.clone(), resource_bounds);
+    assert_eq!(bounded_config.block_max_capacity.n_steps, 10);
+    assert_eq!(bounded_config.block_max_capacity.gas, 100);
+    assert_eq!(
+        bounded_bouncer_config(bouncer_config.clone(), BlockResourceBounds::default()),
+        bouncer_config
+    );
+}
+
+#[test]
+fn test_resource_bounds_limit_block_size() {
+    let (n_loose_txs, loose_weights) = execute_with_resource_bounds(BlockResourceBounds::default());
+    assert_eq!(n_loose_txs, N_BOUNDED_BLOCK_TXS);
+
+    // Only about half of the transactions fit in half of the steps they take together.
+    let tight_bounds =
+        BlockResourceBounds { max_steps: Some(loose_weights.n_steps / 2), max_gas: None };
+    let (n_tight_txs, tight_weights) = execute_with_resource_bounds(tight_bounds);
+    assert!(0 < n_tight_txs && n_tight_txs < n_loose_txs);
+    assert!(tight_weights.n_steps <= loose_weights.n_steps / 2);
+}
+
+// Executes the same transactions in a real executor, whose block capacity is bounded as the block
+// builder factory bounds it. Returns the number of transactions included in the block, and the
+// block's weights.
+fn execute_with_resource_bounds(resource_bounds: BlockResourceBounds) -> (usize, BouncerWeights) {
+    let block_context = BlockContext::create_for_account_testing();
+    let TestInitData { state, account_address, contract_address, mut nonce_manager } =
+        create_test_init_data(block_context.chain_info(), CairoVersion::Cairo1);
+    let block_context = BlockContext::new(
+        block_context.block_info().clone(),
+        block_context.chain_info().clone(),
+        block_context.versioned_constants().clone(),
+        bounded_bouncer_config(BouncerConfig::max(), resource_bounds),
+    );
+    let mut executor =
+        TransactionExecutor::new(state, block_context, TransactionExecutorConfig::default());
+
+    let txs: Vec<BlockifierTransaction> = (0..N_BOUNDED_BLOCK_TXS)
+        .map(|_| {
+            BlockifierTransaction::Account(emit_n_events_tx(
+                1,
+                account_address,
+                contract_address,
+                nonce_manager.next(account_address),
+            ))
+        })
+        .collect();
+    let n_included_txs = executor.add_txs_to_block(&txs).into_iter().filter(Result::is_ok).count();
+    let (_, _, bouncer_weights) = executor.close_block().unwrap();
+    (n_included_txs, bouncer_weights)
+}
+
 fn set_transaction_executor_expectations(
     input_txs: &[Transaction],
     num_txs_to_execute: usize,
@@ -4,6 +4,7 @@ use starknet_batcher_types::communication::{
     BatcherRequestAndResponseSender,
     BatcherResponse,
 };
+use starknet_batcher_types::errors::BatcherError;
 use starknet_mempool_infra::component_definitions::ComponentRequestHandler;
 use starknet_mempool_infra::component_server::LocalComponentServer;
 use tokio::sync::mpsc::Receiver;
@@ -26,16 +27,46 @@ impl ComponentRequestHandler<BatcherRequest, BatcherResponse> for Batcher {
             BatcherRequest::BuildProposal(input) => {
                 BatcherResponse::BuildProposal(self.build_proposal(input).await)
             }
+            BatcherRequest::RebuildProposal(input) => {
+                BatcherResponse::RebuildProposal(self.rebuild_proposal(input).await)
+            }
             BatcherRequest::GetProposalContent(input) => {
                 BatcherResponse::GetProposalContent(self.get_proposal_content(input).await)
             }
+            BatcherRequest::TryGetProposalContent(input) => {
+                BatcherResponse::TryGetProposalContent(self.try_get_proposal_content(input).await)
+            }
             BatcherRequest::StartHeight(input) => {
                 BatcherResponse::StartHeight(self.start_height(input).await)
             }
             BatcherRequest::DecisionReached(input) => {
                 BatcherResponse::DecisionReached(self.decision_reached(input).await)
             }
-            _ => unimplemented!(),
+            BatcherRequest::PreConfirm(input) => {
+                BatcherResponse::PreConfirm(self.pre_confirm(input).await)
+            }
+            BatcherRequest::GetProposalStatus(input) => {
+                BatcherResponse::GetProposalStatus(self.get_proposal_status(input).await)
+            }
+            BatcherRequest::SummarizeTransactions(input) => {
+                BatcherResponse::SummarizeTransactions(self.summarize_transactions(input))
+            }
+            BatcherRequest::ListProposals => {
+                BatcherResponse::ListProposals(self.list_proposals().await)
+            }
+            BatcherRequest::WarmUp(hint) => BatcherResponse::WarmUp(self.warm_up(hint).await),
+            // Proposal validation is not implemented yet; answer with a typed error rather than
+            // panicking.
+            BatcherRequest::ValidateProposal(_) => {
+                BatcherResponse::ValidateProposal(Err(BatcherError::NotSupported {
+                    request: "ValidateProposal".to_string(),
+                }))
+            }
+            BatcherRequest::SendProposalContent(_) => {
+                BatcherResponse::SendProposalContent(Err(BatcherError::NotSupported {
+                    request: "SendProposalContent".to_string(),
+                }))
+            }
         }
     }
 }
@@ -3,17 +3,23 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use blockifier::blockifier::block::BlockNumberHashPair;
+use blockifier::transaction::objects::TransactionExecutionInfo;
 use indexmap::IndexMap;
 use papyrus_config::dumping::{ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
 use starknet_api::block::BlockNumber;
 use starknet_api::block_hash::state_diff_hash::calculate_state_diff_hash;
-use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
 use starknet_api::executable_transaction::Transaction;
 use starknet_api::state::ThinStateDiff;
 use starknet_api::transaction::TransactionHash;
-use starknet_batcher_types::batcher_types::{ProposalCommitment, ProposalId};
+use starknet_batcher_types::batcher_types::{
+    BlockResourceBounds,
+    EventRecord,
+    ProposalCommitment,
+    ProposalId,
+};
 use starknet_mempool_types::communication::{MempoolClientError, SharedMempoolClient};
 use thiserror::Error;
 use tokio::select;
@@ -96,6 +102,8 @@ pub enum BuildProposalError {
     NoActiveHeight,
     #[error("Proposal with id {proposal_id} already exists.")]
     ProposalAlreadyExists { proposal_id: ProposalId },
+    #[error("Ran out of {resource} while building the proposal.")]
+    ResourceExhausted { resource: String },
 }
 
 #[derive(Clone, Debug, Error)]
@@ -117,6 +125,9 @@ pub trait ProposalManagerTrait: Send + Sync {
         proposal_id: ProposalId,
         retrospective_block_hash: Option<BlockNumberHashPair>,
         deadline: tokio::time::Instant,
+        max_txs: Option<usize>,
+        resource_bounds: BlockResourceBounds,
+        seed_txs: Vec<Transaction>,
         tx_sender: tokio::sync::mpsc::UnboundedSender<Transaction>,
     ) -> Result<(), BuildProposalError>;
 
@@ -129,6 +140,10 @@ pub trait ProposalManagerTrait: Send + Sync {
         &self,
         proposal_id: ProposalId,
     ) -> ProposalResult<ProposalCommitment>;
+
+    /// Preloads the given classes for the proposals of the active height. Does nothing if there
+    /// is no active height.
+    async fn warm_up(&self, class_hashes: Vec<ClassHash>);
 }
 
 /// Main struct for handling block proposals.
@@ -161,6 +176,11 @@ pub struct ProposalOutput {
     pub commitment: ProposalCommitment,
     pub tx_hashes: HashSet<TransactionHash>,
     pub nonces: HashMap<ContractAddress, Nonce>,
+    // The execution infos of the proposal's transactions, in execution order; only summarized if
+    // the proposal is committed.
+    pub execution_infos: IndexMap<TransactionHash, TransactionExecutionInfo>,
+    // The events emitted by the proposal's transactions, in execution order.
+    pub events: Vec<EventRecord>,
 }
 
 #[async_trait]
@@ -195,27 +215,45 @@ impl ProposalManagerTrait for ProposalManager {
     /// Starts a new block proposal generation task for the given proposal_id and height with
     /// transactions from the mempool.
     /// Requires tx_sender for sending the generated transactions to the caller.
-    #[instrument(skip(self, tx_sender), err, fields(self.active_height))]
+    /// If max_txs is given, the proposal includes at most that many transactions, and the block
+    /// capacity is capped by the given resource bounds.
+    /// The seed transactions (e.g., of a previous proposal being rebuilt) are executed first,
+    /// ahead of any mempool transactions; fails if they don't fit in max_txs.
+    #[instrument(skip(self, seed_txs, tx_sender), err, fields(self.active_height))]
     async fn build_block_proposal(
         &mut self,
         proposal_id: ProposalId,
         retrospective_block_hash: Option<BlockNumberHashPair>,
         deadline: tokio::time::Instant,
+        max_txs: Option<usize>,
+        resource_bounds: BlockResourceBounds,
+        seed_txs: Vec<Transaction>,
         tx_sender: tokio::sync::mpsc::UnboundedSender<Transaction>,
     ) -> Result<(), BuildProposalError> {
         let height = self.active_height.ok_or(BuildProposalError::NoActiveHeight)?;
         if self.done_proposals.lock().await.contains_key(&proposal_id) {
             return Err(BuildProposalError::ProposalAlreadyExists { proposal_id });
         }
+        if max_txs.is_some_and(|max_txs| seed_txs.len() > max_txs) {
+            return Err(BuildProposalError::ResourceExhausted {
+                resource: "transactions".to_string(),
+            });
+        }
         info!("Starting generation of a new proposal with id {}.", proposal_id);
         self.set_active_proposal(proposal_id).await?;
-        let block_builder =
-            self.block_builder_factory.create_block_builder(height, retrospective_block_hash)?;
+        let block_builder = self.block_builder_factory.create_block_builder(
+            height,
+            retrospective_block_hash,
+            max_txs,
+            resource_bounds,
+        )?;
 
         self.active_proposal_handle = Some(tokio::spawn(
             BuildProposalTask {
                 mempool_client: self.mempool_client.clone(),
+                seed_txs,
                 tx_sender,
+                max_txs,
                 block_builder_next_txs_buffer_size: self.config.block_builder_next_txs_buffer_size,
                 max_txs_per_mempool_request: self.config.max_txs_per_mempool_request,
                 block_builder,
@@ -254,6 +292,14 @@ impl ProposalManagerTrait for ProposalManager {
             Err(e) => Err(e.clone()),
         }
     }
+
+    async fn warm_up(&self, class_hashes: Vec<ClassHash>) {
+        let Some(height) = self.active_height else {
+            debug!("No active height, skipping the warm-up of {} classes.", class_hashes.len());
+            return;
+        };
+        self.block_builder_factory.warm_up(height, &class_hashes);
+    }
 }
 
 impl ProposalManager {
@@ -314,7 +360,11 @@ impl ProposalManager {
 
 struct BuildProposalTask {
     mempool_client: SharedMempoolClient,
+    // Transactions to feed the block builder before any mempool transactions.
+    seed_txs: Vec<Transaction>,
     tx_sender: tokio::sync::mpsc::UnboundedSender<Transaction>,
+    // The maximal number of transactions to feed the block builder, seed transactions included.
+    max_txs: Option<usize>,
     max_txs_per_mempool_request: usize,
     block_builder_next_txs_buffer_size: usize,
     block_builder: Box<dyn BlockBuilderTrait + Send>,
@@ -338,7 +388,9 @@ impl BuildProposalTask {
 
         let feed_mempool_txs_future = Self::feed_mempool_txs(
             &self.mempool_client,
+            self.max_txs,
             self.max_txs_per_mempool_request,
+            std::mem::take(&mut self.seed_txs),
             &mempool_tx_sender,
         );
 
@@ -363,16 +415,39 @@ impl BuildProposalTask {
     }
 
     // TODO: Move this to the batcher.
-    /// Feeds transactions from the mempool to the mempool_tx_sender channel.
+    /// Feeds the seed transactions, then transactions from the mempool, to the mempool_tx_sender
+    /// channel.
+    /// If max_txs is given, at most that many transactions are fed in total, so that no mempool
+    /// transaction is taken only to be left out of the block.
     /// Returns only on error or when the task is cancelled.
     async fn feed_mempool_txs(
         mempool_client: &SharedMempoolClient,
+        max_txs: Option<usize>,
         max_txs_per_mempool_request: usize,
+        seed_txs: Vec<Transaction>,
         mempool_tx_sender: &tokio::sync::mpsc::Sender<Transaction>,
     ) -> GetProposalResultError {
+        if !seed_txs.is_empty() {
+            trace!("Feeding {} seed transactions to the block builder.", seed_txs.len());
+        }
+        let mut n_remaining_txs = max_txs.map(|max_txs| max_txs.saturating_sub(seed_txs.len()));
+        for tx in seed_txs {
+            mempool_tx_sender
+                .send(tx)
+                .await
+                .expect("Channel should remain open during feeding seed transactions.");
+        }
         loop {
+            let n_txs_to_request = match n_remaining_txs {
+                Some(0) => {
+                    trace!("Fed the maximal number of transactions to the block builder.");
+                    return std::future::pending().await;
+                }
+                Some(n_remaining_txs) => n_remaining_txs.min(max_txs_per_mempool_request),
+                None => max_txs_per_mempool_request,
+            };
             // TODO: Get L1 transactions.
-            let mempool_txs = match mempool_client.get_txs(max_txs_per_mempool_request).await {
+            let mempool_txs = match mempool_client.get_txs(n_txs_to_request).await {
                 Ok(txs) if txs.is_empty() => {
                     // TODO: Consider sleeping for a while.
                     tokio::task::yield_now().await;
@@ -388,6 +463,9 @@ impl BuildProposalTask {
                 "Feeding {} transactions from the mempool to the block builder.",
                 mempool_txs.len()
             );
+            if let Some(n_remaining_txs) = n_remaining_txs.as_mut() {
+                *n_remaining_txs = n_remaining_txs.saturating_sub(mempool_txs.len());
+            }
             for tx in mempool_txs {
                 mempool_tx_sender
                     .send(tx)
@@ -431,7 +509,25 @@ impl From<BlockExecutionArtifacts> for ProposalOutput {
         let commitment =
             ProposalCommitment { state_diff_commitment: calculate_state_diff_hash(&state_diff) };
         let tx_hashes = HashSet::from_iter(artifacts.execution_infos.keys().copied());
+        let events = artifacts
+            .execution_infos
+            .iter()
+            .flat_map(|(tx_hash, execution_info)| {
+                execution_info.receipt_events().into_iter().map(|receipt_event| EventRecord {
+                    tx_hash: *tx_hash,
+                    from_address: receipt_event.from_address,
+                    content: receipt_event.content.clone(),
+                })
+            })
+            .collect();
 
-        Self { state_diff, commitment, tx_hashes, nonces }
+        Self {
+            state_diff,
+            commitment,
+            tx_hashes,
+            nonces,
+            execution_infos: artifacts.execution_infos,
+            events,
+        }
     }
 }
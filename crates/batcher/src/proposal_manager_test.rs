@@ -6,10 +6,11 @@ use async_trait::async_trait;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use mockall::automock;
+use mockall::predicate::eq;
 use rstest::{fixture, rstest};
 use starknet_api::block::BlockNumber;
 use starknet_api::executable_transaction::Transaction;
-use starknet_batcher_types::batcher_types::ProposalId;
+use starknet_batcher_types::batcher_types::{BlockResourceBounds, ProposalId};
 use starknet_mempool_types::communication::MockMempoolClient;
 use tokio_stream::StreamExt;
 
@@ -120,7 +121,15 @@ async fn proposal_generation_fails_without_start_height(
     ),
 ) {
     let err = proposal_manager
-        .build_block_proposal(ProposalId(0), None, arbitrary_deadline(), output_streaming.0)
+        .build_block_proposal(
+            ProposalId(0),
+            None,
+            arbitrary_deadline(),
+            None,
+            BlockResourceBounds::default(),
+            vec![],
+            output_streaming.0,
+        )
         .await;
     assert_matches!(err, Err(BuildProposalError::NoActiveHeight));
 }
@@ -141,7 +150,7 @@ async fn proposal_generation_success(
     block_builder_factory
         .expect_create_block_builder()
         .once()
-        .returning(move |_, _| simulate_build_block(Some(n_txs)));
+        .returning(move |_, _, _, _| simulate_build_block(Some(n_txs)));
 
     mempool_client.expect_get_txs().once().returning(|max_n_txs| Ok(test_txs(0..max_n_txs)));
 
@@ -162,13 +171,102 @@ async fn proposal_generation_success(
     proposal_manager.start_height(INITIAL_HEIGHT).await.unwrap();
 
     proposal_manager
-        .build_block_proposal(ProposalId(0), None, arbitrary_deadline(), output_streaming.0)
+        .build_block_proposal(
+            ProposalId(0),
+            None,
+            arbitrary_deadline(),
+            None,
+            BlockResourceBounds::default(),
+            vec![],
+            output_streaming.0,
+        )
         .await
         .unwrap();
 
     proposal_manager.await_active_proposal().await;
 }
 
+#[rstest]
+#[tokio::test]
+async fn proposal_generation_requests_at_most_max_txs(
+    proposal_manager_config: ProposalManagerConfig,
+    mut block_builder_factory: MockBlockBuilderFactoryTrait,
+    mut mempool_client: MockMempoolClient,
+    storage_reader: MockBatcherStorageReaderTrait,
+    output_streaming: (
+        tokio::sync::mpsc::UnboundedSender<Transaction>,
+        tokio::sync::mpsc::UnboundedReceiver<Transaction>,
+    ),
+) {
+    const MAX_TXS: usize = 3;
+    const N_SEED_TXS: usize = 1;
+    block_builder_factory
+        .expect_create_block_builder()
+        .once()
+        .returning(|_, _, _, _| simulate_build_block(Some(MAX_TXS)));
+
+    // Only the transactions missing for the block are requested, and only once.
+    mempool_client
+        .expect_get_txs()
+        .once()
+        .with(eq(MAX_TXS - N_SEED_TXS))
+        .returning(|max_n_txs| Ok(test_txs(N_SEED_TXS..N_SEED_TXS + max_n_txs)));
+
+    let mut proposal_manager = ProposalManager::new(
+        proposal_manager_config,
+        Arc::new(mempool_client),
+        Arc::new(block_builder_factory),
+        Arc::new(storage_reader),
+    );
+
+    proposal_manager.start_height(INITIAL_HEIGHT).await.unwrap();
+
+    proposal_manager
+        .build_block_proposal(
+            ProposalId(0),
+            None,
+            arbitrary_deadline(),
+            Some(MAX_TXS),
+            BlockResourceBounds::default(),
+            test_txs(0..N_SEED_TXS),
+            output_streaming.0,
+        )
+        .await
+        .unwrap();
+
+    proposal_manager.await_active_proposal().await;
+}
+
+#[rstest]
+#[tokio::test]
+async fn proposal_generation_fails_if_seed_txs_exceed_max_txs(
+    mut proposal_manager: ProposalManager,
+    output_streaming: (
+        tokio::sync::mpsc::UnboundedSender<Transaction>,
+        tokio::sync::mpsc::UnboundedReceiver<Transaction>,
+    ),
+) {
+    const MAX_TXS: usize = 1;
+    proposal_manager.start_height(INITIAL_HEIGHT).await.unwrap();
+
+    // No block builder is created, as the seed transactions can't all be included.
+    let err = proposal_manager
+        .build_block_proposal(
+            ProposalId(0),
+            None,
+            arbitrary_deadline(),
+            Some(MAX_TXS),
+            BlockResourceBounds::default(),
+            test_txs(0..MAX_TXS + 1),
+            output_streaming.0,
+        )
+        .await;
+    assert_matches!(
+        err,
+        Err(BuildProposalError::ResourceExhausted { resource }) if resource == "transactions"
+    );
+}
+
 #[rstest]
 #[tokio::test]
 async fn consecutive_proposal_generations_success(
@@ -181,7 +279,7 @@ async fn consecutive_proposal_generations_success(
     block_builder_factory
         .expect_create_block_builder()
         .times(2)
-        .returning(move |_, _| simulate_build_block(Some(n_txs)));
+        .returning(move |_, _, _, _| simulate_build_block(Some(n_txs)));
 
     let expected_txs = test_txs(0..proposal_manager_config.max_txs_per_mempool_request);
     let mempool_txs = expected_txs.clone();
@@ -198,7 +296,15 @@ async fn consecutive_proposal_generations_success(
 
     let (output_sender_0, _rec_0) = output_streaming();
     proposal_manager
-        .build_block_proposal(ProposalId(0), None, arbitrary_deadline(), output_sender_0)
+        .build_block_proposal(
+            ProposalId(0),
+            None,
+            arbitrary_deadline(),
+            None,
+            BlockResourceBounds::default(),
+            vec![],
+            output_sender_0,
+        )
         .await
         .unwrap();
 
@@ -207,7 +313,15 @@ async fn consecutive_proposal_generations_success(
 
     let (output_sender_1, _rec_1) = output_streaming();
     proposal_manager
-        .build_block_proposal(ProposalId(1), None, arbitrary_deadline(), output_sender_1)
+        .build_block_proposal(
+            ProposalId(1),
+            None,
+            arbitrary_deadline(),
+            None,
+            BlockResourceBounds::default(),
+            vec![],
+            output_sender_1,
+        )
         .await
         .unwrap();
 
@@ -227,7 +341,7 @@ async fn multiple_proposals_generation_fail(
     block_builder_factory
         .expect_create_block_builder()
         .once()
-        .returning(|_, _| simulate_build_block(None));
+        .returning(|_, _, _, _| simulate_build_block(None));
 
     mempool_client.expect_get_txs().returning(|_| Ok(vec![]));
 
@@ -243,14 +357,30 @@ async fn multiple_proposals_generation_fail(
     // A proposal that will never finish.
     let (output_sender_0, _rec_0) = output_streaming();
     proposal_manager
-        .build_block_proposal(ProposalId(0), None, arbitrary_deadline(), output_sender_0)
+        .build_block_proposal(
+            ProposalId(0),
+            None,
+            arbitrary_deadline(),
+            None,
+            BlockResourceBounds::default(),
+            vec![],
+            output_sender_0,
+        )
         .await
         .unwrap();
 
     // Try to generate another proposal while the first one is still being generated.
     let (output_sender_1, _rec_1) = output_streaming();
     let another_generate_request = proposal_manager
-        .build_block_proposal(ProposalId(1), None, arbitrary_deadline(), output_sender_1)
+        .build_block_proposal(
+            ProposalId(1),
+            None,
+            arbitrary_deadline(),
+            None,
+            BlockResourceBounds::default(),
+            vec![],
+            output_sender_1,
+        )
         .await;
     assert_matches!(
         another_generate_request,
@@ -275,12 +405,12 @@ async fn test_take_proposal_result_no_active_proposal(
     block_builder_factory
         .expect_create_block_builder()
         .once()
-        .returning(move |_, _| simulate_build_block(Some(n_txs)));
+        .returning(move |_, _, _, _| simulate_build_block(Some(n_txs)));
 
     block_builder_factory
         .expect_create_block_builder()
         .once()
-        .returning(move |_, _| simulate_build_block(Some(n_txs)));
+        .returning(move |_, _, _, _| simulate_build_block(Some(n_txs)));
 
     let expected_txs = test_txs(0..n_txs);
     let mempool_txs = expected_txs.clone();
@@ -296,7 +426,15 @@ async fn test_take_proposal_result_no_active_proposal(
     proposal_manager.start_height(INITIAL_HEIGHT).await.unwrap();
 
     proposal_manager
-        .build_block_proposal(ProposalId(0), None, arbitrary_deadline(), output_sender_0)
+        .build_block_proposal(
+            ProposalId(0),
+            None,
+            arbitrary_deadline(),
+            None,
+            BlockResourceBounds::default(),
+            vec![],
+            output_sender_0,
+        )
         .await
         .unwrap();
 
@@ -304,7 +442,15 @@ async fn test_take_proposal_result_no_active_proposal(
     proposal_manager.await_active_proposal().await;
 
     proposal_manager
-        .build_block_proposal(ProposalId(1), None, arbitrary_deadline(), output_sender_1)
+        .build_block_proposal(
+            ProposalId(1),
+            None,
+            arbitrary_deadline(),
+            None,
+            BlockResourceBounds::default(),
+            vec![],
+            output_sender_1,
+        )
         .await
         .unwrap();
 
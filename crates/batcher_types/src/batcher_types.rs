@@ -1,12 +1,14 @@
 use std::fmt::Debug;
+use std::time::Duration;
 
 use blockifier::blockifier::block::BlockNumberHashPair;
 use chrono::prelude::*;
 use derive_more::Display;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::BlockNumber;
-use starknet_api::core::StateDiffCommitment;
+use starknet_api::core::{ClassHash, ContractAddress, GlobalRoot, StateDiffCommitment};
 use starknet_api::executable_transaction::Transaction;
+use starknet_api::transaction::{EventContent, EventKey, TransactionHash};
 
 use crate::errors::BatcherError;
 
@@ -32,29 +34,126 @@ pub struct ProposalCommitment {
     pub state_diff_commitment: StateDiffCommitment,
 }
 
+/// The point in time by which a request must be served.
+pub type Deadline = chrono::DateTime<Utc>;
+
+/// The urgency of a proposal request. Requests waiting for the batcher are served by priority,
+/// e.g., so that the leader's proposal is built before speculative ones.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize,
+)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// Resource limits of a single block, enforced by the batcher on top of its configured block
+/// capacity; limits not given are left to that capacity.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockResourceBounds {
+    pub max_steps: Option<usize>,
+    pub max_gas: Option<usize>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BuildProposalInput {
     pub proposal_id: ProposalId,
-    pub deadline: chrono::DateTime<Utc>,
+    pub deadline: Deadline,
     pub retrospective_block_hash: Option<BlockNumberHashPair>,
+    /// The urgency of the request, deciding its place among the requests waiting for the batcher.
+    #[serde(default)]
+    pub priority: Priority,
+    /// Opaque data of the caller, echoed back in the proposal's status (e.g., to correlate the
+    /// proposal with the caller's own context).
+    #[serde(default)]
+    pub metadata: Vec<u8>,
+    /// The maximal number of transactions to include in the proposal; unlimited if not given.
+    #[serde(default)]
+    pub max_transactions: Option<usize>,
+    /// Limits on the resources of the proposal's block, below the batcher's block capacity.
+    #[serde(default)]
+    pub resource_bounds: BlockResourceBounds,
     // TODO: Should we get the gas price here?
 }
 
+/// A request to build a new proposal seeded by the transactions of a previous proposal of the
+/// same height, e.g., to refine a proposal when only its deadline changes. The new proposal is
+/// built with the base proposal's other parameters.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RebuildProposalInput {
+    pub proposal_id: ProposalId,
+    pub base_proposal_id: ProposalId,
+    pub deadline: Deadline,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GetProposalContentInput {
     // TBD: We don't really need the proposal_id because there is only one proposal at a time.
     pub proposal_id: ProposalId,
+    // The sequence of the chunk to (re-)send, e.g., to resume a stream that dropped after that
+    // chunk was sent; the stream then continues from the chunk after it. If not given, the next
+    // chunk of the stream is returned.
+    #[serde(default)]
+    pub resume_from: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GetProposalContentResponse {
     pub content: GetProposalContent,
+    // The index of this chunk in the proposal's content. Empty responses carry the index of the
+    // next chunk, as they are not chunks themselves.
+    pub sequence: u64,
+    // Whether this is the final chunk of the proposal's content.
+    pub is_last: bool,
+}
+
+impl GetProposalContentResponse {
+    /// Returns true if the proposal has no content available yet. The caller may retry later.
+    pub fn is_empty(&self) -> bool {
+        matches!(self.content, GetProposalContent::Empty)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum GetProposalContent {
     Txs(Vec<Transaction>),
     Finished(ProposalCommitment),
+    // The proposal is still being built, or queued behind another proposal, and has no content
+    // available yet.
+    Empty,
+}
+
+/// A progress update of a proposal build, see `BatcherClient::build_proposal_with_progress`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalProgress {
+    pub txs_included: usize,
+    // The number of transactions expected by the deadline, extrapolated from the rate so far.
+    pub estimated_total: usize,
+    pub elapsed: Duration,
+}
+
+impl ProposalProgress {
+    pub fn new(txs_included: usize, elapsed: Duration, time_budget: Duration) -> Self {
+        let estimated_total = if elapsed.is_zero() {
+            txs_included
+        } else {
+            let txs_included_wide =
+                u128::try_from(txs_included).expect("Transaction counts fit in u128.");
+            let extrapolated = txs_included_wide * time_budget.as_nanos() / elapsed.as_nanos();
+            usize::try_from(extrapolated).unwrap_or(usize::MAX).max(txs_included)
+        };
+        Self { txs_included, estimated_total, elapsed }
+    }
+
+    /// Returns the share of the estimated total already included, in percent.
+    pub fn percent(&self) -> usize {
+        if self.estimated_total == 0 {
+            return 0;
+        }
+        self.txs_included.saturating_mul(100) / self.estimated_total
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -110,4 +209,76 @@ pub struct DecisionReachedInput {
     pub proposal_id: ProposalId,
 }
 
+/// The outcome of a decision. Re-sending a decision for an already committed proposal does not
+/// commit it again, and returns the original outcome with `already_committed` set. Committed
+/// proposals are tracked in memory until the next height starts, so re-sent decisions are only
+/// detected within the same height and batcher run.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DecisionReachedResponse {
+    pub already_committed: bool,
+    // The height at which the proposal was committed.
+    pub height: BlockNumber,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PreConfirmInput {
+    pub proposal_id: ProposalId,
+}
+
+/// A tentative signal that the given proposal is expected to be decided, sent ahead of the
+/// decision itself. Unlike a decision, it commits nothing, and the state root may still change.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PreConfirmation {
+    pub proposal_id: ProposalId,
+    pub tentative_state_root: GlobalRoot,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetProposalStatusInput {
+    pub proposal_id: ProposalId,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetProposalStatusResponse {
+    pub status: ProposalStatus,
+    // The metadata the proposal was built with; empty for proposals not built by the batcher.
+    pub metadata: Vec<u8>,
+    // The number of transactions included in the proposal, as streamed so far.
+    pub n_txs: usize,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SummarizeTransactionsInput {
+    pub tx_hashes: Vec<TransactionHash>,
+}
+
+/// What the batcher should preload ahead of upcoming proposals.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarmUpHint {
+    /// Contract classes to load into the batcher's class cache.
+    pub class_hashes: Vec<ClassHash>,
+}
+
+/// An event emitted by a committed transaction.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub tx_hash: TransactionHash,
+    pub from_address: ContractAddress,
+    pub content: EventContent,
+}
+
+/// Selects events by emitting contract and/or first key; unset criteria match any event.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventFilter {
+    pub contract_address: Option<ContractAddress>,
+    pub first_key: Option<EventKey>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &EventRecord) -> bool {
+        self.contract_address.map_or(true, |address| address == event.from_address)
+            && self.first_key.as_ref().map_or(true, |key| event.content.keys.first() == Some(key))
+    }
+}
+
 pub type BatcherResult<T> = Result<T, BatcherError>;
@@ -0,0 +1,43 @@
+use tokio::runtime::Handle;
+
+use crate::batcher_types::{
+    BuildProposalInput,
+    DecisionReachedInput,
+    DecisionReachedResponse,
+    GetProposalContentInput,
+    GetProposalContentResponse,
+};
+use crate::communication::{BatcherClientResult, SharedBatcherClient};
+
+/// A synchronous wrapper over a batcher client, for callers that are not async (e.g., CLIs).
+/// Requests are driven to completion on the given runtime handle.
+///
+/// Note that blocking on a runtime handle panics when called from within an async context.
+pub struct BlockingBatcherClient {
+    client: SharedBatcherClient,
+    handle: Handle,
+}
+
+impl BlockingBatcherClient {
+    pub fn new(client: SharedBatcherClient, handle: Handle) -> Self {
+        Self { client, handle }
+    }
+
+    pub fn build_proposal_blocking(&self, input: BuildProposalInput) -> BatcherClientResult<()> {
+        self.handle.block_on(self.client.build_proposal(input))
+    }
+
+    pub fn get_stream_content_blocking(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<GetProposalContentResponse> {
+        self.handle.block_on(self.client.get_proposal_content(input))
+    }
+
+    pub fn decision_reached_blocking(
+        &self,
+        input: DecisionReachedInput,
+    ) -> BatcherClientResult<DecisionReachedResponse> {
+        self.handle.block_on(self.client.decision_reached(input))
+    }
+}
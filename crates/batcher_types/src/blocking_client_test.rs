@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use starknet_api::block::BlockNumber;
+
+use crate::batcher_types::{DecisionReachedInput, DecisionReachedResponse, ProposalId};
+use crate::blocking_client::BlockingBatcherClient;
+use crate::communication::MockBatcherClient;
+
+#[test]
+fn decision_reached_from_sync_context() {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+    let mut client = MockBatcherClient::new();
+    client
+        .expect_decision_reached()
+        .withf(|input| input.proposal_id == PROPOSAL_ID)
+        .times(1)
+        .returning(|_| {
+            Ok(DecisionReachedResponse { already_committed: false, height: BlockNumber(0) })
+        });
+
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let blocking_client = BlockingBatcherClient::new(Arc::new(client), runtime.handle().clone());
+    blocking_client
+        .decision_reached_blocking(DecisionReachedInput { proposal_id: PROPOSAL_ID })
+        .unwrap();
+}
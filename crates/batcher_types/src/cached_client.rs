@@ -0,0 +1,135 @@
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use blockifier::execution::call_info::PortableSummary;
+use lru::LruCache;
+
+use crate::batcher_types::{
+    BuildProposalInput,
+    DecisionReachedInput,
+    DecisionReachedResponse,
+    GetProposalContentInput,
+    GetProposalContentResponse,
+    GetProposalStatusInput,
+    GetProposalStatusResponse,
+    PreConfirmInput,
+    PreConfirmation,
+    ProposalId,
+    ProposalStatus,
+    RebuildProposalInput,
+    SendProposalContentInput,
+    SendProposalContentResponse,
+    StartHeightInput,
+    SummarizeTransactionsInput,
+    ValidateProposalInput,
+    WarmUpHint,
+};
+use crate::communication::{BatcherClient, BatcherClientResult, SharedBatcherClient};
+
+/// A batcher client that memoizes proposal statuses for a configurable time-to-live, to reduce
+/// the load on the batcher when the same proposal is queried repeatedly. Stale entries are
+/// refetched, and errors are never cached. All other requests are forwarded as is.
+pub struct CachedBatcherClient {
+    client: SharedBatcherClient,
+    ttl: Duration,
+    proposal_statuses: Mutex<LruCache<ProposalId, (Instant, GetProposalStatusResponse)>>,
+}
+
+impl CachedBatcherClient {
+    pub fn new(client: SharedBatcherClient, ttl: Duration, capacity: NonZeroUsize) -> Self {
+        Self { client, ttl, proposal_statuses: Mutex::new(LruCache::new(capacity)) }
+    }
+
+    fn cached_proposal_status(&self, proposal_id: ProposalId) -> Option<GetProposalStatusResponse> {
+        let mut proposal_statuses = self.proposal_statuses.lock().expect("Lock was poisoned");
+        match proposal_statuses.get(&proposal_id) {
+            Some((fetched_at, status)) if fetched_at.elapsed() < self.ttl => Some(status.clone()),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl BatcherClient for CachedBatcherClient {
+    async fn build_proposal(&self, input: BuildProposalInput) -> BatcherClientResult<()> {
+        self.client.build_proposal(input).await
+    }
+
+    async fn rebuild_proposal(&self, input: RebuildProposalInput) -> BatcherClientResult<()> {
+        self.client.rebuild_proposal(input).await
+    }
+
+    async fn get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<GetProposalContentResponse> {
+        self.client.get_proposal_content(input).await
+    }
+
+    async fn try_get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<Option<GetProposalContentResponse>> {
+        self.client.try_get_proposal_content(input).await
+    }
+
+    async fn validate_proposal(&self, input: ValidateProposalInput) -> BatcherClientResult<()> {
+        self.client.validate_proposal(input).await
+    }
+
+    async fn send_proposal_content(
+        &self,
+        input: SendProposalContentInput,
+    ) -> BatcherClientResult<SendProposalContentResponse> {
+        self.client.send_proposal_content(input).await
+    }
+
+    async fn start_height(&self, input: StartHeightInput) -> BatcherClientResult<()> {
+        self.client.start_height(input).await
+    }
+
+    async fn decision_reached(
+        &self,
+        input: DecisionReachedInput,
+    ) -> BatcherClientResult<DecisionReachedResponse> {
+        self.client.decision_reached(input).await
+    }
+
+    async fn pre_confirm(&self, input: PreConfirmInput) -> BatcherClientResult<PreConfirmation> {
+        self.client.pre_confirm(input).await
+    }
+
+    async fn summarize_transactions(
+        &self,
+        input: SummarizeTransactionsInput,
+    ) -> BatcherClientResult<Vec<PortableSummary>> {
+        self.client.summarize_transactions(input).await
+    }
+
+    async fn get_proposal_status(
+        &self,
+        input: GetProposalStatusInput,
+    ) -> BatcherClientResult<GetProposalStatusResponse> {
+        let proposal_id = input.proposal_id;
+        if let Some(status) = self.cached_proposal_status(proposal_id) {
+            return Ok(status);
+        }
+
+        let status = self.client.get_proposal_status(input).await?;
+        self.proposal_statuses
+            .lock()
+            .expect("Lock was poisoned")
+            .put(proposal_id, (Instant::now(), status.clone()));
+        Ok(status)
+    }
+
+    async fn list_proposals(&self) -> BatcherClientResult<Vec<(ProposalId, ProposalStatus)>> {
+        self.client.list_proposals().await
+    }
+
+    async fn warm_up(&self, hint: WarmUpHint) -> BatcherClientResult<()> {
+        self.client.warm_up(hint).await
+    }
+}
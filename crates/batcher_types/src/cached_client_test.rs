@@ -0,0 +1,40 @@
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+
+use crate::batcher_types::{
+    GetProposalStatusInput,
+    GetProposalStatusResponse,
+    ProposalId,
+    ProposalStatus,
+};
+use crate::cached_client::CachedBatcherClient;
+use crate::communication::{BatcherClient, MockBatcherClient};
+
+#[tokio::test]
+async fn proposal_status_is_fetched_once_within_ttl() {
+    const PROPOSAL_ID: ProposalId = ProposalId(0);
+    let mut client = MockBatcherClient::new();
+    client.expect_get_proposal_status().times(1).returning(|_| {
+        Ok(GetProposalStatusResponse {
+            status: ProposalStatus::Processing,
+            metadata: vec![],
+            n_txs: 0,
+        })
+    });
+
+    let cached_client = CachedBatcherClient::new(
+        Arc::new(client),
+        Duration::from_secs(60),
+        NonZeroUsize::new(10).unwrap(),
+    );
+    for _ in 0..2 {
+        let status = cached_client
+            .get_proposal_status(GetProposalStatusInput { proposal_id: PROPOSAL_ID })
+            .await
+            .unwrap();
+        assert_matches!(status.status, ProposalStatus::Processing);
+    }
+}
@@ -1,10 +1,14 @@
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use blockifier::execution::call_info::PortableSummary;
+use futures::future::join_all;
 use mockall::predicate::*;
 use mockall::*;
 use papyrus_proc_macros::handle_response_variants;
 use serde::{Deserialize, Serialize};
+use starknet_api::executable_transaction::Transaction;
 use starknet_mempool_infra::component_client::{
     ClientError,
     LocalComponentClient,
@@ -12,17 +16,32 @@ use starknet_mempool_infra::component_client::{
 };
 use starknet_mempool_infra::component_definitions::ComponentRequestAndResponseSender;
 use thiserror::Error;
+use tokio::sync::mpsc;
 
 use crate::batcher_types::{
     BatcherResult,
     BuildProposalInput,
+    Deadline,
     DecisionReachedInput,
+    DecisionReachedResponse,
+    GetProposalContent,
     GetProposalContentInput,
     GetProposalContentResponse,
+    GetProposalStatusInput,
+    GetProposalStatusResponse,
+    PreConfirmInput,
+    PreConfirmation,
+    ProposalCommitment,
+    ProposalId,
+    ProposalProgress,
+    ProposalStatus,
+    RebuildProposalInput,
     SendProposalContentInput,
     SendProposalContentResponse,
     StartHeightInput,
+    SummarizeTransactionsInput,
     ValidateProposalInput,
+    WarmUpHint,
 };
 use crate::errors::BatcherError;
 
@@ -33,6 +52,9 @@ pub type BatcherRequestAndResponseSender =
     ComponentRequestAndResponseSender<BatcherRequest, BatcherResponse>;
 pub type SharedBatcherClient = Arc<dyn BatcherClient>;
 
+// The time to wait before asking again for the content of a proposal that has none yet.
+const SLEEP_BETWEEN_EMPTY_CONTENT_REQUESTS: Duration = Duration::from_millis(10);
+
 /// Serves as the batcher's shared interface. Requires `Send + Sync` to allow transferring and
 /// sharing resources (inputs, futures) across threads.
 #[automock]
@@ -40,12 +62,76 @@ pub type SharedBatcherClient = Arc<dyn BatcherClient>;
 pub trait BatcherClient: Send + Sync {
     /// Starts the process of building a proposal.
     async fn build_proposal(&self, input: BuildProposalInput) -> BatcherClientResult<()>;
+    /// Starts building all the given proposals concurrently, under a deadline shared by all of
+    /// them (overriding the inputs' own deadlines). Returns the result of each build, in input
+    /// order; builds not started by the deadline fail with
+    /// [BatcherClientError::DeadlineExceeded]. Fails as a whole if the deadline already passed.
+    async fn build_proposals(
+        &self,
+        inputs: Vec<BuildProposalInput>,
+        deadline: Deadline,
+    ) -> BatcherClientResult<Vec<BatcherClientResult<()>>> {
+        let time_to_deadline = (deadline - chrono::Utc::now())
+            .to_std()
+            .map_err(|_| BatcherClientError::DeadlineExceeded { deadline })?;
+        let builds = inputs.into_iter().map(|input| async move {
+            let build = self.build_proposal(BuildProposalInput { deadline, ..input });
+            tokio::time::timeout(time_to_deadline, build)
+                .await
+                .map_err(|_| BatcherClientError::DeadlineExceeded { deadline })?
+        });
+        Ok(join_all(builds).await)
+    }
+    /// Builds a proposal and streams its content to completion, pushing a progress update to
+    /// `sink` after each content chunk. Returns the proposal's transactions and commitment. As the
+    /// content is consumed here, it must not be requested by anyone else meanwhile.
+    async fn build_proposal_with_progress(
+        &self,
+        input: BuildProposalInput,
+        sink: mpsc::Sender<ProposalProgress>,
+    ) -> BatcherClientResult<(Vec<Transaction>, ProposalCommitment)> {
+        let proposal_id = input.proposal_id;
+        let time_budget = (input.deadline - chrono::Utc::now()).to_std().unwrap_or_default();
+        let started_at = Instant::now();
+        self.build_proposal(input).await?;
+
+        let mut txs = Vec::new();
+        loop {
+            let response = self
+                .get_proposal_content(GetProposalContentInput { proposal_id, resume_from: None })
+                .await?;
+            match response.content {
+                GetProposalContent::Txs(chunk) => {
+                    txs.extend(chunk);
+                    let progress =
+                        ProposalProgress::new(txs.len(), started_at.elapsed(), time_budget);
+                    // The listener may be gone; the build goes on regardless.
+                    let _ = sink.send(progress).await;
+                }
+                GetProposalContent::Finished(commitment) => return Ok((txs, commitment)),
+                // The proposal has no content available yet, ask again shortly.
+                GetProposalContent::Empty => {
+                    tokio::time::sleep(SLEEP_BETWEEN_EMPTY_CONTENT_REQUESTS).await
+                }
+            }
+        }
+    }
+    /// Starts building a new proposal seeded by the transactions streamed so far for the base
+    /// proposal, which are executed ahead of any new mempool transactions. The new proposal is
+    /// streamed as any other, under its own ID.
+    async fn rebuild_proposal(&self, input: RebuildProposalInput) -> BatcherClientResult<()>;
     /// Gets the next available content from the proposal stream (only relevant when building a
     /// proposal).
     async fn get_proposal_content(
         &self,
         input: GetProposalContentInput,
     ) -> BatcherClientResult<GetProposalContentResponse>;
+    /// Like [Self::get_proposal_content], but returns `None` instead of waiting if no content is
+    /// available yet (e.g., the proposal is still being built), for polling loops.
+    async fn try_get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<Option<GetProposalContentResponse>>;
     /// Starts the process of validating a proposal.
     async fn validate_proposal(&self, input: ValidateProposalInput) -> BatcherClientResult<()>;
     /// Sends the content of a proposal. Only relevant when validating a proposal.
@@ -65,35 +151,144 @@ pub trait BatcherClient: Send + Sync {
     async fn start_height(&self, input: StartHeightInput) -> BatcherClientResult<()>;
     /// Notifies the batcher that a decision has been reached.
     /// This closes the process of the given height, and the accepted proposal is committed.
-    async fn decision_reached(&self, input: DecisionReachedInput) -> BatcherClientResult<()>;
+    /// Idempotent within a height: a decision on a proposal already committed since the height
+    /// started returns the original outcome (see [DecisionReachedResponse]).
+    async fn decision_reached(
+        &self,
+        input: DecisionReachedInput,
+    ) -> BatcherClientResult<DecisionReachedResponse>;
+    /// Requests a pre-confirmation of the given proposal, carrying its tentative state root,
+    /// ahead of the decision. Unlike [Self::decision_reached], this does not commit the proposal.
+    async fn pre_confirm(&self, input: PreConfirmInput) -> BatcherClientResult<PreConfirmation>;
+    /// Returns the execution summaries of the given committed transactions, in the order they
+    /// were requested, in their portable form. The summaries are computed by the batcher, so the
+    /// full execution infos are not sent over the wire.
+    async fn summarize_transactions(
+        &self,
+        input: SummarizeTransactionsInput,
+    ) -> BatcherClientResult<Vec<PortableSummary>>;
+    /// Returns the status of the given proposal, along with the metadata it was built with.
+    async fn get_proposal_status(
+        &self,
+        input: GetProposalStatusInput,
+    ) -> BatcherClientResult<GetProposalStatusResponse>;
+    /// Lists the proposals currently held by the batcher, including the queued ones, along with
+    /// their statuses.
+    async fn list_proposals(&self) -> BatcherClientResult<Vec<(ProposalId, ProposalStatus)>>;
+    /// Signals the batcher to preload the hinted contract classes, e.g., ahead of a burst of
+    /// transactions, so that the following proposals don't wait on loading them from storage.
+    /// Best effort: classes that fail to load are skipped.
+    async fn warm_up(&self, hint: WarmUpHint) -> BatcherClientResult<()>;
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum BatcherRequest {
     BuildProposal(BuildProposalInput),
+    RebuildProposal(RebuildProposalInput),
     GetProposalContent(GetProposalContentInput),
+    TryGetProposalContent(GetProposalContentInput),
     ValidateProposal(ValidateProposalInput),
     SendProposalContent(SendProposalContentInput),
     StartHeight(StartHeightInput),
     DecisionReached(DecisionReachedInput),
+    PreConfirm(PreConfirmInput),
+    SummarizeTransactions(SummarizeTransactionsInput),
+    GetProposalStatus(GetProposalStatusInput),
+    ListProposals,
+    WarmUp(WarmUpHint),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum BatcherResponse {
     BuildProposal(BatcherResult<()>),
+    RebuildProposal(BatcherResult<()>),
     GetProposalContent(BatcherResult<GetProposalContentResponse>),
+    TryGetProposalContent(BatcherResult<Option<GetProposalContentResponse>>),
     ValidateProposal(BatcherResult<()>),
     SendProposalContent(BatcherResult<SendProposalContentResponse>),
     StartHeight(BatcherResult<()>),
-    DecisionReached(BatcherResult<()>),
+    DecisionReached(BatcherResult<DecisionReachedResponse>),
+    PreConfirm(BatcherResult<PreConfirmation>),
+    SummarizeTransactions(BatcherResult<Vec<PortableSummary>>),
+    GetProposalStatus(BatcherResult<GetProposalStatusResponse>),
+    ListProposals(BatcherResult<Vec<(ProposalId, ProposalStatus)>>),
+    WarmUp(BatcherResult<()>),
 }
 
 #[derive(Clone, Debug, Error)]
 pub enum BatcherClientError {
     #[error(transparent)]
-    ClientError(#[from] ClientError),
+    ClientError(ClientError),
     #[error(transparent)]
     BatcherError(#[from] BatcherError),
+    #[error("Expected proposal content chunk {expected}, got chunk {received}.")]
+    ChunkGap { expected: u64, received: u64 },
+    #[error("The request was not answered within {timeout:?}.")]
+    Timeout { timeout: Duration },
+    #[error("The request was not answered by its deadline, {deadline}.")]
+    DeadlineExceeded { deadline: Deadline },
+    #[error("The response has {size} bytes, more than the limit of {limit} bytes.")]
+    ResponseTooLarge { size: usize, limit: usize },
+}
+
+impl From<ClientError> for BatcherClientError {
+    fn from(err: ClientError) -> Self {
+        match err {
+            ClientError::ResponseTooLarge { size, limit } => Self::ResponseTooLarge { size, limit },
+            err => Self::ClientError(err),
+        }
+    }
+}
+
+/// Verifies that the proposal content chunks returned by consecutive
+/// [BatcherClient::get_proposal_content] calls are contiguous.
+#[derive(Debug, Default)]
+pub struct ChunkSequenceVerifier {
+    next_sequence: u64,
+    finished: bool,
+}
+
+impl ChunkSequenceVerifier {
+    /// Checks the given response against the previously verified ones. Empty responses are not
+    /// chunks, and are only checked not to skip ahead.
+    pub fn verify(&mut self, response: &GetProposalContentResponse) -> BatcherClientResult<()> {
+        if response.sequence != self.next_sequence {
+            return Err(BatcherClientError::ChunkGap {
+                expected: self.next_sequence,
+                received: response.sequence,
+            });
+        }
+        if !response.is_empty() {
+            self.next_sequence += 1;
+            self.finished = response.is_last;
+        }
+        Ok(())
+    }
+
+    /// Returns true once the last chunk has been verified.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+/// A batcher client backed by either a local or a remote component client. Allows writing code
+/// against both backends without resorting to dynamic dispatch.
+#[derive(Clone)]
+pub enum AnyBatcherClient {
+    Local(LocalBatcherClient),
+    Remote(RemoteBatcherClient),
+}
+
+impl From<LocalBatcherClient> for AnyBatcherClient {
+    fn from(client: LocalBatcherClient) -> Self {
+        Self::Local(client)
+    }
+}
+
+impl From<RemoteBatcherClient> for AnyBatcherClient {
+    fn from(client: RemoteBatcherClient) -> Self {
+        Self::Remote(client)
+    }
 }
 
 #[async_trait]
@@ -104,6 +299,17 @@ impl BatcherClient for LocalBatcherClient {
         handle_response_variants!(BatcherResponse, BuildProposal, BatcherClientError, BatcherError)
     }
 
+    async fn rebuild_proposal(&self, input: RebuildProposalInput) -> BatcherClientResult<()> {
+        let request = BatcherRequest::RebuildProposal(input);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            BatcherResponse,
+            RebuildProposal,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
     async fn get_proposal_content(
         &self,
         input: GetProposalContentInput,
@@ -118,6 +324,20 @@ impl BatcherClient for LocalBatcherClient {
         )
     }
 
+    async fn try_get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<Option<GetProposalContentResponse>> {
+        let request = BatcherRequest::TryGetProposalContent(input);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            BatcherResponse,
+            TryGetProposalContent,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
     async fn validate_proposal(&self, input: ValidateProposalInput) -> BatcherClientResult<()> {
         let request = BatcherRequest::ValidateProposal(input);
         let response = self.send(request).await;
@@ -149,7 +369,10 @@ impl BatcherClient for LocalBatcherClient {
         handle_response_variants!(BatcherResponse, StartHeight, BatcherClientError, BatcherError)
     }
 
-    async fn decision_reached(&self, input: DecisionReachedInput) -> BatcherClientResult<()> {
+    async fn decision_reached(
+        &self,
+        input: DecisionReachedInput,
+    ) -> BatcherClientResult<DecisionReachedResponse> {
         let request = BatcherRequest::DecisionReached(input);
         let response = self.send(request).await;
         handle_response_variants!(
@@ -159,22 +382,84 @@ impl BatcherClient for LocalBatcherClient {
             BatcherError
         )
     }
+
+    async fn pre_confirm(&self, input: PreConfirmInput) -> BatcherClientResult<PreConfirmation> {
+        let request = BatcherRequest::PreConfirm(input);
+        let response = self.send(request).await;
+        handle_response_variants!(BatcherResponse, PreConfirm, BatcherClientError, BatcherError)
+    }
+
+    async fn summarize_transactions(
+        &self,
+        input: SummarizeTransactionsInput,
+    ) -> BatcherClientResult<Vec<PortableSummary>> {
+        let request = BatcherRequest::SummarizeTransactions(input);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            BatcherResponse,
+            SummarizeTransactions,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_proposal_status(
+        &self,
+        input: GetProposalStatusInput,
+    ) -> BatcherClientResult<GetProposalStatusResponse> {
+        let request = BatcherRequest::GetProposalStatus(input);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            BatcherResponse,
+            GetProposalStatus,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn list_proposals(&self) -> BatcherClientResult<Vec<(ProposalId, ProposalStatus)>> {
+        let request = BatcherRequest::ListProposals;
+        let response = self.send(request).await;
+        handle_response_variants!(BatcherResponse, ListProposals, BatcherClientError, BatcherError)
+    }
+
+    async fn warm_up(&self, hint: WarmUpHint) -> BatcherClientResult<()> {
+        let request = BatcherRequest::WarmUp(hint);
+        let response = self.send(request).await;
+        handle_response_variants!(BatcherResponse, WarmUp, BatcherClientError, BatcherError)
+    }
 }
 
+// Requests whose repeated delivery could repeat their effect are sent once, regardless of the
+// configured retries; this follows the policy of the retrying client (see retry_client.rs).
 #[async_trait]
 impl BatcherClient for RemoteBatcherClient {
     async fn build_proposal(&self, input: BuildProposalInput) -> BatcherClientResult<()> {
         let request = BatcherRequest::BuildProposal(input);
-        let response = self.send(request).await?;
+        let response = self.send_once(request).await?;
         handle_response_variants!(BatcherResponse, BuildProposal, BatcherClientError, BatcherError)
     }
 
+    async fn rebuild_proposal(&self, input: RebuildProposalInput) -> BatcherClientResult<()> {
+        let request = BatcherRequest::RebuildProposal(input);
+        let response = self.send_once(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            RebuildProposal,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
     async fn get_proposal_content(
         &self,
         input: GetProposalContentInput,
     ) -> BatcherClientResult<GetProposalContentResponse> {
-        let request = BatcherRequest::GetProposalContent(input);
-        let response = self.send(request).await?;
+        // Without a chunk to resume from, a repeated request would skip a chunk.
+        let response = match input.resume_from {
+            Some(_) => self.send(BatcherRequest::GetProposalContent(input)).await?,
+            None => self.send_once(BatcherRequest::GetProposalContent(input)).await?,
+        };
         handle_response_variants!(
             BatcherResponse,
             GetProposalContent,
@@ -183,9 +468,26 @@ impl BatcherClient for RemoteBatcherClient {
         )
     }
 
+    async fn try_get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<Option<GetProposalContentResponse>> {
+        // Without a chunk to resume from, a repeated request would skip a chunk.
+        let response = match input.resume_from {
+            Some(_) => self.send(BatcherRequest::TryGetProposalContent(input)).await?,
+            None => self.send_once(BatcherRequest::TryGetProposalContent(input)).await?,
+        };
+        handle_response_variants!(
+            BatcherResponse,
+            TryGetProposalContent,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
     async fn validate_proposal(&self, input: ValidateProposalInput) -> BatcherClientResult<()> {
         let request = BatcherRequest::ValidateProposal(input);
-        let response = self.send(request).await?;
+        let response = self.send_once(request).await?;
         handle_response_variants!(
             BatcherResponse,
             ValidateProposal,
@@ -199,7 +501,7 @@ impl BatcherClient for RemoteBatcherClient {
         input: SendProposalContentInput,
     ) -> BatcherClientResult<SendProposalContentResponse> {
         let request = BatcherRequest::SendProposalContent(input);
-        let response = self.send(request).await?;
+        let response = self.send_once(request).await?;
         handle_response_variants!(
             BatcherResponse,
             SendProposalContent,
@@ -210,11 +512,14 @@ impl BatcherClient for RemoteBatcherClient {
 
     async fn start_height(&self, input: StartHeightInput) -> BatcherClientResult<()> {
         let request = BatcherRequest::StartHeight(input);
-        let response = self.send(request).await?;
+        let response = self.send_once(request).await?;
         handle_response_variants!(BatcherResponse, StartHeight, BatcherClientError, BatcherError)
     }
 
-    async fn decision_reached(&self, input: DecisionReachedInput) -> BatcherClientResult<()> {
+    async fn decision_reached(
+        &self,
+        input: DecisionReachedInput,
+    ) -> BatcherClientResult<DecisionReachedResponse> {
         let request = BatcherRequest::DecisionReached(input);
         let response = self.send(request).await?;
         handle_response_variants!(
@@ -224,4 +529,162 @@ impl BatcherClient for RemoteBatcherClient {
             BatcherError
         )
     }
+
+    async fn pre_confirm(&self, input: PreConfirmInput) -> BatcherClientResult<PreConfirmation> {
+        let request = BatcherRequest::PreConfirm(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(BatcherResponse, PreConfirm, BatcherClientError, BatcherError)
+    }
+
+    async fn summarize_transactions(
+        &self,
+        input: SummarizeTransactionsInput,
+    ) -> BatcherClientResult<Vec<PortableSummary>> {
+        let request = BatcherRequest::SummarizeTransactions(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            SummarizeTransactions,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_proposal_status(
+        &self,
+        input: GetProposalStatusInput,
+    ) -> BatcherClientResult<GetProposalStatusResponse> {
+        let request = BatcherRequest::GetProposalStatus(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            GetProposalStatus,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn list_proposals(&self) -> BatcherClientResult<Vec<(ProposalId, ProposalStatus)>> {
+        let request = BatcherRequest::ListProposals;
+        let response = self.send(request).await?;
+        handle_response_variants!(BatcherResponse, ListProposals, BatcherClientError, BatcherError)
+    }
+
+    async fn warm_up(&self, hint: WarmUpHint) -> BatcherClientResult<()> {
+        let request = BatcherRequest::WarmUp(hint);
+        let response = self.send(request).await?;
+        handle_response_variants!(BatcherResponse, WarmUp, BatcherClientError, BatcherError)
+    }
+}
+
+#[async_trait]
+impl BatcherClient for AnyBatcherClient {
+    async fn build_proposal(&self, input: BuildProposalInput) -> BatcherClientResult<()> {
+        match self {
+            Self::Local(client) => client.build_proposal(input).await,
+            Self::Remote(client) => client.build_proposal(input).await,
+        }
+    }
+
+    async fn rebuild_proposal(&self, input: RebuildProposalInput) -> BatcherClientResult<()> {
+        match self {
+            Self::Local(client) => client.rebuild_proposal(input).await,
+            Self::Remote(client) => client.rebuild_proposal(input).await,
+        }
+    }
+
+    async fn get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<GetProposalContentResponse> {
+        match self {
+            Self::Local(client) => client.get_proposal_content(input).await,
+            Self::Remote(client) => client.get_proposal_content(input).await,
+        }
+    }
+
+    async fn try_get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<Option<GetProposalContentResponse>> {
+        match self {
+            Self::Local(client) => client.try_get_proposal_content(input).await,
+            Self::Remote(client) => client.try_get_proposal_content(input).await,
+        }
+    }
+
+    async fn validate_proposal(&self, input: ValidateProposalInput) -> BatcherClientResult<()> {
+        match self {
+            Self::Local(client) => client.validate_proposal(input).await,
+            Self::Remote(client) => client.validate_proposal(input).await,
+        }
+    }
+
+    async fn send_proposal_content(
+        &self,
+        input: SendProposalContentInput,
+    ) -> BatcherClientResult<SendProposalContentResponse> {
+        match self {
+            Self::Local(client) => client.send_proposal_content(input).await,
+            Self::Remote(client) => client.send_proposal_content(input).await,
+        }
+    }
+
+    async fn start_height(&self, input: StartHeightInput) -> BatcherClientResult<()> {
+        match self {
+            Self::Local(client) => client.start_height(input).await,
+            Self::Remote(client) => client.start_height(input).await,
+        }
+    }
+
+    async fn decision_reached(
+        &self,
+        input: DecisionReachedInput,
+    ) -> BatcherClientResult<DecisionReachedResponse> {
+        match self {
+            Self::Local(client) => client.decision_reached(input).await,
+            Self::Remote(client) => client.decision_reached(input).await,
+        }
+    }
+
+    async fn pre_confirm(&self, input: PreConfirmInput) -> BatcherClientResult<PreConfirmation> {
+        match self {
+            Self::Local(client) => client.pre_confirm(input).await,
+            Self::Remote(client) => client.pre_confirm(input).await,
+        }
+    }
+
+    async fn summarize_transactions(
+        &self,
+        input: SummarizeTransactionsInput,
+    ) -> BatcherClientResult<Vec<PortableSummary>> {
+        match self {
+            Self::Local(client) => client.summarize_transactions(input).await,
+            Self::Remote(client) => client.summarize_transactions(input).await,
+        }
+    }
+
+    async fn get_proposal_status(
+        &self,
+        input: GetProposalStatusInput,
+    ) -> BatcherClientResult<GetProposalStatusResponse> {
+        match self {
+            Self::Local(client) => client.get_proposal_status(input).await,
+            Self::Remote(client) => client.get_proposal_status(input).await,
+        }
+    }
+
+    async fn list_proposals(&self) -> BatcherClientResult<Vec<(ProposalId, ProposalStatus)>> {
+        match self {
+            Self::Local(client) => client.list_proposals().await,
+            Self::Remote(client) => client.list_proposals().await,
+        }
+    }
+
+    async fn warm_up(&self, hint: WarmUpHint) -> BatcherClientResult<()> {
+        match self {
+            Self::Local(client) => client.warm_up(hint).await,
+            Self::Remote(client) => client.warm_up(hint).await,
+        }
+    }
 }
@@ -0,0 +1,293 @@
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Response, Server};
+use starknet_api::core::GlobalRoot;
+use starknet_api::felt;
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    RemoteClientConfig,
+};
+use starknet_mempool_infra::serde_utils::BincodeSerdeWrapper;
+use tokio::sync::mpsc::channel;
+
+use crate::batcher_types::{
+    BlockResourceBounds,
+    BuildProposalInput,
+    GetProposalContent,
+    GetProposalContentInput,
+    GetProposalContentResponse,
+    PreConfirmInput,
+    PreConfirmation,
+    Priority,
+    ProposalCommitment,
+    ProposalId,
+    ProposalProgress,
+    ProposalStatus,
+};
+use crate::communication::{
+    AnyBatcherClient,
+    BatcherClient,
+    BatcherClientError,
+    BatcherRequest,
+    BatcherRequestAndResponseSender,
+    BatcherResponse,
+    ChunkSequenceVerifier,
+    LocalBatcherClient,
+    RemoteBatcherClient,
+};
+use crate::errors::BatcherError;
+
+#[tokio::test]
+async fn any_batcher_client_with_local_backend() {
+    let (tx_batcher, mut rx_batcher) = channel::<BatcherRequestAndResponseSender>(1);
+    tokio::spawn(async move {
+        let ComponentRequestAndResponseSender { request, tx } = rx_batcher.recv().await.unwrap();
+        assert_matches!(
+            request,
+            BatcherRequest::BuildProposal(input) if input.proposal_id == ProposalId(1)
+        );
+        tx.send(BatcherResponse::BuildProposal(Ok(()))).await.unwrap();
+    });
+
+    let client = AnyBatcherClient::from(LocalBatcherClient::new(tx_batcher));
+    client
+        .build_proposal(BuildProposalInput {
+            proposal_id: ProposalId(1),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn build_proposals_with_shared_deadline() {
+    const SLOW_PROPOSAL_ID: ProposalId = ProposalId(1);
+    let (tx_batcher, mut rx_batcher) = channel::<BatcherRequestAndResponseSender>(1);
+    tokio::spawn(async move {
+        while let Some(ComponentRequestAndResponseSender { request, tx }) = rx_batcher.recv().await
+        {
+            let BatcherRequest::BuildProposal(input) = request else {
+                panic!("Expected only build proposal requests.");
+            };
+            tokio::spawn(async move {
+                if input.proposal_id == SLOW_PROPOSAL_ID {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+                // The client may have stopped waiting for the response.
+                let _ = tx.send(BatcherResponse::BuildProposal(Ok(()))).await;
+            });
+        }
+    });
+
+    let client = LocalBatcherClient::new(tx_batcher);
+    let deadline = chrono::Utc::now() + chrono::Duration::milliseconds(100);
+    let inputs = (0..3)
+        .map(|id| BuildProposalInput {
+            proposal_id: ProposalId(id),
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(10),
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+        })
+        .collect();
+    let results = client.build_proposals(inputs, deadline).await.unwrap();
+
+    assert_matches!(results[0], Ok(()));
+    assert_matches!(
+        results[1],
+        Err(BatcherClientError::DeadlineExceeded { deadline: missed_deadline })
+            if missed_deadline == deadline
+    );
+    assert_matches!(results[2], Ok(()));
+}
+
+#[tokio::test]
+async fn build_proposals_past_deadline() {
+    let (tx_batcher, _rx_batcher) = channel::<BatcherRequestAndResponseSender>(1);
+    let client = LocalBatcherClient::new(tx_batcher);
+    let deadline = chrono::Utc::now() - chrono::Duration::seconds(1);
+    assert_matches!(
+        client.build_proposals(Vec::new(), deadline).await,
+        Err(BatcherClientError::DeadlineExceeded { .. })
+    );
+}
+
+#[tokio::test]
+async fn unknown_proposal_error_reaches_the_client() {
+    const PROPOSAL_ID: ProposalId = ProposalId(1);
+    let (tx_batcher, mut rx_batcher) = channel::<BatcherRequestAndResponseSender>(1);
+    tokio::spawn(async move {
+        let ComponentRequestAndResponseSender { tx, .. } = rx_batcher.recv().await.unwrap();
+        let error = BatcherError::UnknownProposal { proposal_id: PROPOSAL_ID };
+        tx.send(BatcherResponse::GetProposalContent(Err(error))).await.unwrap();
+    });
+
+    let client = LocalBatcherClient::new(tx_batcher);
+    let result = client
+        .get_proposal_content(GetProposalContentInput {
+            proposal_id: PROPOSAL_ID,
+            resume_from: None,
+        })
+        .await;
+    assert_matches!(
+        result,
+        Err(BatcherClientError::BatcherError(BatcherError::UnknownProposal { proposal_id }))
+            if proposal_id == PROPOSAL_ID
+    );
+}
+
+#[tokio::test]
+async fn remote_client_rejects_responses_over_the_size_limit() {
+    let proposals = (0..100).map(|id| (ProposalId(id), ProposalStatus::Processing)).collect();
+    let response_bytes = BincodeSerdeWrapper::new(BatcherResponse::ListProposals(Ok(proposals)))
+        .to_bincode()
+        .unwrap();
+    let response_size = response_bytes.len();
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let socket = listener.local_addr().unwrap();
+    let make_service = make_service_fn(move |_connection| {
+        let response_bytes = response_bytes.clone();
+        async move {
+            Ok::<_, hyper::Error>(service_fn(move |_request| {
+                let response = Response::new(Body::from(response_bytes.clone()));
+                async move { Ok::<_, hyper::Error>(response) }
+            }))
+        }
+    });
+    tokio::spawn(Server::from_tcp(listener).unwrap().serve(make_service));
+
+    let config = RemoteClientConfig { socket, ..Default::default() };
+    let client = RemoteBatcherClient::new(config.clone()).with_max_response_bytes(response_size);
+    assert_eq!(client.list_proposals().await.unwrap().len(), 100);
+
+    let client = RemoteBatcherClient::new(config).with_max_response_bytes(response_size - 1);
+    assert_matches!(
+        client.list_proposals().await,
+        Err(BatcherClientError::ResponseTooLarge { size, limit })
+            if size == response_size && limit == response_size - 1
+    );
+}
+
+#[tokio::test]
+async fn pre_confirm_round_trip() {
+    const PROPOSAL_ID: ProposalId = ProposalId(1);
+    let pre_confirmation = PreConfirmation {
+        proposal_id: PROPOSAL_ID,
+        tentative_state_root: GlobalRoot(felt!("0x7")),
+    };
+    let expected_pre_confirmation = pre_confirmation.clone();
+    let (tx_batcher, mut rx_batcher) = channel::<BatcherRequestAndResponseSender>(1);
+    tokio::spawn(async move {
+        let ComponentRequestAndResponseSender { request, tx } = rx_batcher.recv().await.unwrap();
+        assert_matches!(
+            request,
+            BatcherRequest::PreConfirm(input) if input.proposal_id == PROPOSAL_ID
+        );
+        tx.send(BatcherResponse::PreConfirm(Ok(pre_confirmation))).await.unwrap();
+    });
+
+    let client = LocalBatcherClient::new(tx_batcher);
+    let result = client.pre_confirm(PreConfirmInput { proposal_id: PROPOSAL_ID }).await.unwrap();
+    assert_eq!(result, expected_pre_confirmation);
+}
+
+#[tokio::test]
+async fn build_proposal_with_progress() {
+    const N_CHUNKS: u64 = 2;
+    let (tx_batcher, mut rx_batcher) = channel::<BatcherRequestAndResponseSender>(1);
+    tokio::spawn(async move {
+        let mut sequence = 0;
+        while let Some(ComponentRequestAndResponseSender { request, tx }) = rx_batcher.recv().await
+        {
+            let response = match request {
+                BatcherRequest::BuildProposal(_) => BatcherResponse::BuildProposal(Ok(())),
+                BatcherRequest::GetProposalContent(_) => {
+                    let is_last = sequence == N_CHUNKS;
+                    let content = if is_last {
+                        GetProposalContent::Finished(ProposalCommitment::default())
+                    } else {
+                        GetProposalContent::Txs(Vec::new())
+                    };
+                    sequence += 1;
+                    BatcherResponse::GetProposalContent(Ok(GetProposalContentResponse {
+                        content,
+                        sequence: sequence - 1,
+                        is_last,
+                    }))
+                }
+                _ => panic!("Expected only build proposal and get content requests."),
+            };
+            tx.send(response).await.unwrap();
+        }
+    });
+
+    let client = LocalBatcherClient::new(tx_batcher);
+    let (progress_sender, mut progress_receiver) = channel(10);
+    let (txs, commitment) = client
+        .build_proposal_with_progress(
+            BuildProposalInput {
+                proposal_id: ProposalId(1),
+                deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+                retrospective_block_hash: None,
+                priority: Priority::Normal,
+                metadata: vec![],
+                max_transactions: None,
+                resource_bounds: BlockResourceBounds::default(),
+            },
+            progress_sender,
+        )
+        .await
+        .unwrap();
+
+    assert!(txs.is_empty());
+    assert_eq!(commitment, ProposalCommitment::default());
+    let mut progress_updates = Vec::new();
+    while let Ok(progress) = progress_receiver.try_recv() {
+        progress_updates.push(progress);
+    }
+    assert_eq!(progress_updates.len(), 2);
+    assert!(progress_updates[0].elapsed <= progress_updates[1].elapsed);
+}
+
+#[test]
+fn proposal_progress_extrapolates_to_the_time_budget() {
+    let progress = ProposalProgress::new(10, Duration::from_secs(1), Duration::from_secs(4));
+    assert_eq!(progress.estimated_total, 40);
+    assert_eq!(progress.percent(), 25);
+}
+
+#[test]
+fn chunk_sequence_verifier() {
+    let txs_chunk = |sequence| GetProposalContentResponse {
+        content: GetProposalContent::Txs(Vec::new()),
+        sequence,
+        is_last: false,
+    };
+
+    let mut verifier = ChunkSequenceVerifier::default();
+    verifier.verify(&txs_chunk(0)).unwrap();
+    assert_matches!(
+        verifier.verify(&txs_chunk(2)),
+        Err(BatcherClientError::ChunkGap { expected: 1, received: 2 })
+    );
+
+    verifier.verify(&txs_chunk(1)).unwrap();
+    let last_chunk = GetProposalContentResponse {
+        content: GetProposalContent::Finished(ProposalCommitment::default()),
+        sequence: 2,
+        is_last: true,
+    };
+    assert!(!verifier.is_finished());
+    verifier.verify(&last_chunk).unwrap();
+    assert!(verifier.is_finished());
+}
@@ -1,6 +1,7 @@
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::BlockNumber;
+use starknet_api::transaction::TransactionHash;
 use thiserror::Error;
 
 use crate::batcher_types::ProposalId;
@@ -24,6 +25,8 @@ pub enum BatcherError {
     InternalError,
     #[error("Attempt to start proposal with no active height.")]
     NoActiveHeight,
+    #[error("The batcher does not support {request} requests.")]
+    NotSupported { request: String },
     #[error(
         "There is already an active proposal {}, can't start proposal {}.",
         active_proposal_id,
@@ -36,6 +39,13 @@ pub enum BatcherError {
     ProposalFailed,
     #[error("Proposal with ID {proposal_id} not found.")]
     ProposalNotFound { proposal_id: ProposalId },
+    #[error(
+        "Can't resume proposal {proposal_id} from chunk {resume_from}, only {n_sent_chunks} \
+         chunks were sent."
+    )]
+    ResumeFromUnsentChunk { proposal_id: ProposalId, resume_from: u64, n_sent_chunks: u64 },
+    #[error("The batcher ran out of {resource}.")]
+    ResourceExhausted { resource: String },
     #[error(
         "Storage is not synced. Storage height: {storage_height}, requested height: \
          {requested_height}."
@@ -43,4 +53,34 @@ pub enum BatcherError {
     StorageNotSynced { storage_height: BlockNumber, requested_height: BlockNumber },
     #[error("Time to deadline is out of range. Got {deadline}.")]
     TimeToDeadlineError { deadline: chrono::DateTime<Utc> },
+    #[error("Transaction with hash {tx_hash} was not committed by the batcher.")]
+    TransactionNotFound { tx_hash: TransactionHash },
+    #[error("Proposal with ID {proposal_id} was never started at the current height.")]
+    UnknownProposal { proposal_id: ProposalId },
+}
+
+impl BatcherError {
+    /// Returns true if the failed request may succeed when retried as is, without waiting for the
+    /// batcher's load to drop.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            // The active proposal or the sync may be done by the time of the retry.
+            BatcherError::DoneProposalNotFound { .. }
+            | BatcherError::StorageNotSynced { .. } => true,
+            BatcherError::AlreadyWorkingOnHeight { .. }
+            | BatcherError::HeightAlreadyPassed { .. }
+            | BatcherError::InternalError
+            | BatcherError::NoActiveHeight
+            | BatcherError::NotSupported { .. }
+            | BatcherError::ProposalAlreadyExists { .. }
+            | BatcherError::ProposalFailed
+            | BatcherError::ProposalNotFound { .. }
+            | BatcherError::ResumeFromUnsentChunk { .. }
+            | BatcherError::ResourceExhausted { .. }
+            | BatcherError::ServerBusy { .. }
+            | BatcherError::TimeToDeadlineError { .. }
+            | BatcherError::TransactionNotFound { .. }
+            | BatcherError::UnknownProposal { .. } => false,
+        }
+    }
 }
@@ -0,0 +1,129 @@
+use std::future::Future;
+
+use async_trait::async_trait;
+use blockifier::execution::call_info::PortableSummary;
+
+use crate::batcher_types::{
+    BuildProposalInput,
+    DecisionReachedInput,
+    DecisionReachedResponse,
+    GetProposalContentInput,
+    GetProposalContentResponse,
+    GetProposalStatusInput,
+    GetProposalStatusResponse,
+    PreConfirmInput,
+    PreConfirmation,
+    ProposalId,
+    ProposalStatus,
+    RebuildProposalInput,
+    SendProposalContentInput,
+    SendProposalContentResponse,
+    StartHeightInput,
+    SummarizeTransactionsInput,
+    ValidateProposalInput,
+    WarmUpHint,
+};
+use crate::communication::{BatcherClient, BatcherClientResult, SharedBatcherClient};
+use crate::retry_client::is_transport_error;
+
+/// A batcher client that sends each request to `primary` and, if it fails with a transport error
+/// (see [is_transport_error]), sends it again to `secondary`; e.g., a remote batcher backed by a
+/// local one in a single-process deployment. Errors returned by the batcher itself are returned
+/// as is.
+///
+/// Note that the two clients are expected to reach different batchers: a request whose response
+/// was lost may have taken effect on the primary batcher.
+pub struct FallbackBatcherClient {
+    primary: SharedBatcherClient,
+    secondary: SharedBatcherClient,
+}
+
+impl FallbackBatcherClient {
+    pub fn new(primary: SharedBatcherClient, secondary: SharedBatcherClient) -> Self {
+        Self { primary, secondary }
+    }
+
+    async fn with_fallback<'a, T, F, R>(&'a self, request: F) -> BatcherClientResult<T>
+    where
+        F: Fn(&'a SharedBatcherClient) -> R,
+        R: Future<Output = BatcherClientResult<T>>,
+    {
+        match request(&self.primary).await {
+            Err(err) if is_transport_error(&err) => request(&self.secondary).await,
+            result => result,
+        }
+    }
+}
+
+#[async_trait]
+impl BatcherClient for FallbackBatcherClient {
+    async fn build_proposal(&self, input: BuildProposalInput) -> BatcherClientResult<()> {
+        self.with_fallback(|client| client.build_proposal(input.clone())).await
+    }
+
+    async fn rebuild_proposal(&self, input: RebuildProposalInput) -> BatcherClientResult<()> {
+        self.with_fallback(|client| client.rebuild_proposal(input.clone())).await
+    }
+
+    async fn get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<GetProposalContentResponse> {
+        self.with_fallback(|client| client.get_proposal_content(input.clone())).await
+    }
+
+    async fn try_get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<Option<GetProposalContentResponse>> {
+        self.with_fallback(|client| client.try_get_proposal_content(input.clone())).await
+    }
+
+    async fn validate_proposal(&self, input: ValidateProposalInput) -> BatcherClientResult<()> {
+        self.with_fallback(|client| client.validate_proposal(input.clone())).await
+    }
+
+    async fn send_proposal_content(
+        &self,
+        input: SendProposalContentInput,
+    ) -> BatcherClientResult<SendProposalContentResponse> {
+        self.with_fallback(|client| client.send_proposal_content(input.clone())).await
+    }
+
+    async fn start_height(&self, input: StartHeightInput) -> BatcherClientResult<()> {
+        self.with_fallback(|client| client.start_height(input.clone())).await
+    }
+
+    async fn decision_reached(
+        &self,
+        input: DecisionReachedInput,
+    ) -> BatcherClientResult<DecisionReachedResponse> {
+        self.with_fallback(|client| client.decision_reached(input.clone())).await
+    }
+
+    async fn pre_confirm(&self, input: PreConfirmInput) -> BatcherClientResult<PreConfirmation> {
+        self.with_fallback(|client| client.pre_confirm(input.clone())).await
+    }
+
+    async fn summarize_transactions(
+        &self,
+        input: SummarizeTransactionsInput,
+    ) -> BatcherClientResult<Vec<PortableSummary>> {
+        self.with_fallback(|client| client.summarize_transactions(input.clone())).await
+    }
+
+    async fn get_proposal_status(
+        &self,
+        input: GetProposalStatusInput,
+    ) -> BatcherClientResult<GetProposalStatusResponse> {
+        self.with_fallback(|client| client.get_proposal_status(input.clone())).await
+    }
+
+    async fn list_proposals(&self) -> BatcherClientResult<Vec<(ProposalId, ProposalStatus)>> {
+        self.with_fallback(|client| client.list_proposals()).await
+    }
+
+    async fn warm_up(&self, hint: WarmUpHint) -> BatcherClientResult<()> {
+        self.with_fallback(|client| client.warm_up(hint.clone())).await
+    }
+}
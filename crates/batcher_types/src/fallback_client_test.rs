@@ -0,0 +1,58 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+
+use crate::batcher_types::{
+    GetProposalStatusInput,
+    GetProposalStatusResponse,
+    ProposalId,
+    ProposalStatus,
+};
+use crate::communication::{BatcherClient, BatcherClientError, MockBatcherClient};
+use crate::errors::BatcherError;
+use crate::fallback_client::FallbackBatcherClient;
+
+const PROPOSAL_ID: ProposalId = ProposalId(0);
+
+fn get_proposal_status_input() -> GetProposalStatusInput {
+    GetProposalStatusInput { proposal_id: PROPOSAL_ID }
+}
+
+#[tokio::test]
+async fn transport_error_falls_back_to_secondary() {
+    let mut primary = MockBatcherClient::new();
+    primary
+        .expect_get_proposal_status()
+        .times(1)
+        .returning(|_| Err(BatcherClientError::Timeout { timeout: Duration::from_secs(1) }));
+    let mut secondary = MockBatcherClient::new();
+    secondary.expect_get_proposal_status().times(1).returning(|_| {
+        Ok(GetProposalStatusResponse {
+            status: ProposalStatus::Processing,
+            metadata: vec![],
+            n_txs: 0,
+        })
+    });
+
+    let client = FallbackBatcherClient::new(Arc::new(primary), Arc::new(secondary));
+    let response = client.get_proposal_status(get_proposal_status_input()).await.unwrap();
+    assert_matches!(response.status, ProposalStatus::Processing);
+}
+
+#[tokio::test]
+async fn batcher_error_does_not_fall_back() {
+    let mut primary = MockBatcherClient::new();
+    primary
+        .expect_get_proposal_status()
+        .times(1)
+        .returning(|_| Err(BatcherError::UnknownProposal { proposal_id: PROPOSAL_ID }.into()));
+    let mut secondary = MockBatcherClient::new();
+    secondary.expect_get_proposal_status().never();
+
+    let client = FallbackBatcherClient::new(Arc::new(primary), Arc::new(secondary));
+    assert_matches!(
+        client.get_proposal_status(get_proposal_status_input()).await,
+        Err(BatcherClientError::BatcherError(BatcherError::UnknownProposal { .. }))
+    );
+}
@@ -1,3 +1,26 @@
 pub mod batcher_types;
+pub mod blocking_client;
+#[cfg(test)]
+mod blocking_client_test;
+pub mod cached_client;
+#[cfg(test)]
+mod cached_client_test;
 pub mod communication;
+#[cfg(test)]
+mod communication_test;
 pub mod errors;
+pub mod fallback_client;
+#[cfg(test)]
+mod fallback_client_test;
+pub mod retry_client;
+#[cfg(test)]
+mod retry_client_test;
+pub mod rpc_server;
+#[cfg(test)]
+mod rpc_server_test;
+pub mod timeout_client;
+#[cfg(test)]
+mod timeout_client_test;
+pub mod tracing_client;
+#[cfg(test)]
+mod tracing_client_test;
@@ -0,0 +1,174 @@
+use std::future::Future;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use blockifier::execution::call_info::PortableSummary;
+use starknet_mempool_infra::component_client::ClientError;
+
+use crate::batcher_types::{
+    BuildProposalInput,
+    DecisionReachedInput,
+    DecisionReachedResponse,
+    GetProposalContentInput,
+    GetProposalContentResponse,
+    GetProposalStatusInput,
+    GetProposalStatusResponse,
+    PreConfirmInput,
+    PreConfirmation,
+    ProposalId,
+    ProposalStatus,
+    RebuildProposalInput,
+    SendProposalContentInput,
+    SendProposalContentResponse,
+    StartHeightInput,
+    SummarizeTransactionsInput,
+    ValidateProposalInput,
+    WarmUpHint,
+};
+use crate::communication::{
+    BatcherClient,
+    BatcherClientError,
+    BatcherClientResult,
+    SharedBatcherClient,
+};
+
+/// A batcher client that retries requests failing with a transport error, as long as a retry
+/// can't repeat the request's effect:
+/// - Queries (e.g., [BatcherClient::get_proposal_status]) are retried freely.
+/// - Decisions are retried, as the batcher commits each proposal once, keyed by its ID.
+/// - Content requests are retried only if they resume from a given chunk; otherwise, a lost
+///   response would skip a chunk.
+/// - Requests that start, feed or switch the work of the batcher (e.g.,
+///   [BatcherClient::build_proposal]) are never retried, as there is no way to abort the original
+///   request first.
+///
+/// Retries are delayed by a backoff, doubled after each retry. Note that remote clients also retry
+/// the requests retried here on their own, unless configured otherwise.
+pub struct RetryingBatcherClient {
+    client: SharedBatcherClient,
+    max_retries: usize,
+    backoff: Duration,
+}
+
+/// The delay before the first retry of a [RetryingBatcherClient], unless set otherwise.
+pub const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+impl RetryingBatcherClient {
+    pub fn new(client: SharedBatcherClient, max_retries: usize) -> Self {
+        Self { client, max_retries, backoff: DEFAULT_RETRY_BACKOFF }
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    async fn with_retries<T, F, R>(&self, mut request: F) -> BatcherClientResult<T>
+    where
+        F: FnMut() -> R,
+        R: Future<Output = BatcherClientResult<T>>,
+    {
+        let mut n_retries = 0;
+        let mut backoff = self.backoff;
+        loop {
+            match request().await {
+                Err(err) if is_transport_error(&err) && n_retries < self.max_retries => {
+                    n_retries += 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2);
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Returns true if the request may not have reached the batcher, or its response was lost.
+pub fn is_transport_error(err: &BatcherClientError) -> bool {
+    matches!(
+        err,
+        BatcherClientError::ClientError(
+            ClientError::CommunicationFailure(_) | ClientError::ResponseParsingFailure(_)
+        ) | BatcherClientError::Timeout { .. }
+    )
+}
+
+#[async_trait]
+impl BatcherClient for RetryingBatcherClient {
+    async fn build_proposal(&self, input: BuildProposalInput) -> BatcherClientResult<()> {
+        self.client.build_proposal(input).await
+    }
+
+    async fn rebuild_proposal(&self, input: RebuildProposalInput) -> BatcherClientResult<()> {
+        self.client.rebuild_proposal(input).await
+    }
+
+    async fn get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<GetProposalContentResponse> {
+        if input.resume_from.is_none() {
+            return self.client.get_proposal_content(input).await;
+        }
+        self.with_retries(|| self.client.get_proposal_content(input.clone())).await
+    }
+
+    async fn try_get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<Option<GetProposalContentResponse>> {
+        if input.resume_from.is_none() {
+            return self.client.try_get_proposal_content(input).await;
+        }
+        self.with_retries(|| self.client.try_get_proposal_content(input.clone())).await
+    }
+
+    async fn validate_proposal(&self, input: ValidateProposalInput) -> BatcherClientResult<()> {
+        self.client.validate_proposal(input).await
+    }
+
+    async fn send_proposal_content(
+        &self,
+        input: SendProposalContentInput,
+    ) -> BatcherClientResult<SendProposalContentResponse> {
+        self.client.send_proposal_content(input).await
+    }
+
+    async fn start_height(&self, input: StartHeightInput) -> BatcherClientResult<()> {
+        self.client.start_height(input).await
+    }
+
+    async fn decision_reached(
+        &self,
+        input: DecisionReachedInput,
+    ) -> BatcherClientResult<DecisionReachedResponse> {
+        self.with_retries(|| self.client.decision_reached(input.clone())).await
+    }
+
+    async fn pre_confirm(&self, input: PreConfirmInput) -> BatcherClientResult<PreConfirmation> {
+        self.with_retries(|| self.client.pre_confirm(input.clone())).await
+    }
+
+    async fn summarize_transactions(
+        &self,
+        input: SummarizeTransactionsInput,
+    ) -> BatcherClientResult<Vec<PortableSummary>> {
+        self.with_retries(|| self.client.summarize_transactions(input.clone())).await
+    }
+
+    async fn get_proposal_status(
+        &self,
+        input: GetProposalStatusInput,
+    ) -> BatcherClientResult<GetProposalStatusResponse> {
+        self.with_retries(|| self.client.get_proposal_status(input.clone())).await
+    }
+
+    async fn list_proposals(&self) -> BatcherClientResult<Vec<(ProposalId, ProposalStatus)>> {
+        self.with_retries(|| self.client.list_proposals()).await
+    }
+
+    async fn warm_up(&self, hint: WarmUpHint) -> BatcherClientResult<()> {
+        self.with_retries(|| self.client.warm_up(hint.clone())).await
+    }
+}
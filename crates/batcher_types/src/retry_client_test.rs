@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+use mockall::Sequence;
+
+use crate::batcher_types::{
+    BlockResourceBounds,
+    BuildProposalInput,
+    GetProposalStatusInput,
+    GetProposalStatusResponse,
+    Priority,
+    ProposalId,
+    ProposalStatus,
+};
+use crate::communication::{BatcherClient, BatcherClientError, MockBatcherClient};
+use crate::retry_client::RetryingBatcherClient;
+
+const PROPOSAL_ID: ProposalId = ProposalId(0);
+const MAX_RETRIES: usize = 3;
+const BACKOFF: Duration = Duration::from_millis(10);
+
+fn transport_error() -> BatcherClientError {
+    BatcherClientError::Timeout { timeout: Duration::from_secs(1) }
+}
+
+#[tokio::test]
+async fn build_proposal_is_not_retried() {
+    let mut client = MockBatcherClient::new();
+    client.expect_build_proposal().times(1).returning(|_| Err(transport_error()));
+
+    let retrying_client = RetryingBatcherClient::new(Arc::new(client), MAX_RETRIES);
+    let result = retrying_client
+        .build_proposal(BuildProposalInput {
+            proposal_id: PROPOSAL_ID,
+            deadline: chrono::Utc::now() + chrono::Duration::seconds(1),
+            retrospective_block_hash: None,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
+        })
+        .await;
+    assert_matches!(result, Err(BatcherClientError::Timeout { .. }));
+}
+
+#[tokio::test]
+async fn get_proposal_status_is_retried() {
+    let mut client = MockBatcherClient::new();
+    let mut sequence = Sequence::new();
+    client
+        .expect_get_proposal_status()
+        .times(1)
+        .in_sequence(&mut sequence)
+        .returning(|_| Err(transport_error()));
+    client.expect_get_proposal_status().times(1).in_sequence(&mut sequence).returning(|_| {
+        Ok(GetProposalStatusResponse {
+            status: ProposalStatus::Processing,
+            metadata: vec![],
+            n_txs: 0,
+        })
+    });
+
+    let retrying_client =
+        RetryingBatcherClient::new(Arc::new(client), MAX_RETRIES).with_backoff(BACKOFF);
+    let start = tokio::time::Instant::now();
+    let response = retrying_client
+        .get_proposal_status(GetProposalStatusInput { proposal_id: PROPOSAL_ID })
+        .await
+        .unwrap();
+    assert_matches!(response.status, ProposalStatus::Processing);
+    // The retry waits for the backoff.
+    assert!(start.elapsed() >= BACKOFF);
+}
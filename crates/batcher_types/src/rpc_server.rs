@@ -0,0 +1,76 @@
+use std::fmt::Display;
+use std::net::SocketAddr;
+
+use jsonrpsee::core::{async_trait, RpcResult};
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::server::{ServerBuilder, ServerHandle};
+use jsonrpsee::types::error::ErrorCode;
+use jsonrpsee::types::ErrorObjectOwned;
+
+use crate::batcher_types::{
+    BuildProposalInput,
+    GetProposalStatusInput,
+    ProposalId,
+    ProposalStatus,
+};
+use crate::communication::SharedBatcherClient;
+
+/// The JSON-RPC methods exposed by [BatcherRpcServer], under the `batcher` namespace (e.g.,
+/// `batcher_getProposalStatus`).
+#[rpc(server, namespace = "batcher")]
+pub trait BatcherJsonRpc {
+    #[method(name = "buildProposal")]
+    async fn build_proposal(&self, input: BuildProposalInput) -> RpcResult<()>;
+
+    #[method(name = "getProposalStatus")]
+    async fn get_proposal_status(&self, proposal_id: ProposalId) -> RpcResult<ProposalStatus>;
+
+    #[method(name = "listProposals")]
+    async fn list_proposals(&self) -> RpcResult<Vec<(ProposalId, ProposalStatus)>>;
+}
+
+/// A thin JSON-RPC facade over a batcher client, for external tools that cannot link against the
+/// Rust client.
+pub struct BatcherRpcServer {
+    client: SharedBatcherClient,
+}
+
+impl BatcherRpcServer {
+    pub fn new(client: SharedBatcherClient) -> Self {
+        Self { client }
+    }
+
+    /// Starts serving on the given address; returns the bound address and the server's handle.
+    pub async fn run(
+        self,
+        address: SocketAddr,
+    ) -> Result<(SocketAddr, ServerHandle), jsonrpsee::core::Error> {
+        let server = ServerBuilder::default().build(address).await?;
+        let local_address = server.local_addr()?;
+        let handle = server.start(self.into_rpc());
+        Ok((local_address, handle))
+    }
+}
+
+#[async_trait]
+impl BatcherJsonRpcServer for BatcherRpcServer {
+    async fn build_proposal(&self, input: BuildProposalInput) -> RpcResult<()> {
+        self.client.build_proposal(input).await.map_err(internal_error)
+    }
+
+    async fn get_proposal_status(&self, proposal_id: ProposalId) -> RpcResult<ProposalStatus> {
+        self.client
+            .get_proposal_status(GetProposalStatusInput { proposal_id })
+            .await
+            .map(|response| response.status)
+            .map_err(internal_error)
+    }
+
+    async fn list_proposals(&self) -> RpcResult<Vec<(ProposalId, ProposalStatus)>> {
+        self.client.list_proposals().await.map_err(internal_error)
+    }
+}
+
+fn internal_error(err: impl Display) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(ErrorCode::InternalError.code(), err.to_string(), None::<()>)
+}
@@ -0,0 +1,47 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use hyper::{Body, Client, Request};
+use serde_json::{json, Value};
+
+use crate::batcher_types::{GetProposalStatusResponse, ProposalId, ProposalStatus};
+use crate::communication::MockBatcherClient;
+use crate::rpc_server::BatcherRpcServer;
+
+#[tokio::test]
+async fn get_proposal_status_over_json_rpc() {
+    const PROPOSAL_ID: ProposalId = ProposalId(7);
+    let mut client = MockBatcherClient::new();
+    client
+        .expect_get_proposal_status()
+        .withf(|input| input.proposal_id == PROPOSAL_ID)
+        .times(1)
+        .returning(|_| {
+            Ok(GetProposalStatusResponse {
+                status: ProposalStatus::Processing,
+                metadata: vec![],
+                n_txs: 0,
+            })
+        });
+
+    let (address, _handle) = BatcherRpcServer::new(Arc::new(client))
+        .run(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .unwrap();
+
+    let request_body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "batcher_getProposalStatus",
+        "params": [PROPOSAL_ID.0],
+    });
+    let request = Request::post(format!("http://{address}"))
+        .header("content-type", "application/json")
+        .body(Body::from(request_body.to_string()))
+        .unwrap();
+    let response = Client::new().request(request).await.unwrap();
+    let response_body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+    let response: Value = serde_json::from_slice(&response_body).unwrap();
+    assert_eq!(response, json!({ "jsonrpc": "2.0", "id": 1, "result": "Processing" }));
+}
@@ -0,0 +1,158 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use blockifier::execution::call_info::PortableSummary;
+
+use crate::batcher_types::{
+    BuildProposalInput,
+    DecisionReachedInput,
+    DecisionReachedResponse,
+    GetProposalContentInput,
+    GetProposalContentResponse,
+    GetProposalStatusInput,
+    GetProposalStatusResponse,
+    PreConfirmInput,
+    PreConfirmation,
+    ProposalId,
+    ProposalStatus,
+    RebuildProposalInput,
+    SendProposalContentInput,
+    SendProposalContentResponse,
+    StartHeightInput,
+    SummarizeTransactionsInput,
+    ValidateProposalInput,
+    WarmUpHint,
+};
+use crate::communication::{
+    BatcherClient,
+    BatcherClientError,
+    BatcherClientResult,
+    SharedBatcherClient,
+};
+
+/// A batcher client that fails every request not answered within the given timeout with
+/// [BatcherClientError::Timeout]. Usually obtained through [BatcherClientExt::with_timeout].
+pub struct TimeoutBatcherClient {
+    client: SharedBatcherClient,
+    timeout: Duration,
+}
+
+impl TimeoutBatcherClient {
+    pub fn new(client: SharedBatcherClient, timeout: Duration) -> Self {
+        Self { client, timeout }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns a client with the given timeout over the same inner client; the timeout replaces
+    /// this client's rather than stacking on top of it.
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        Self::new(self.client.clone(), timeout)
+    }
+
+    async fn with_deadline<T>(
+        &self,
+        request: impl Future<Output = BatcherClientResult<T>>,
+    ) -> BatcherClientResult<T> {
+        tokio::time::timeout(self.timeout, request)
+            .await
+            .map_err(|_| BatcherClientError::Timeout { timeout: self.timeout })?
+    }
+}
+
+#[async_trait]
+impl BatcherClient for TimeoutBatcherClient {
+    async fn build_proposal(&self, input: BuildProposalInput) -> BatcherClientResult<()> {
+        self.with_deadline(self.client.build_proposal(input)).await
+    }
+
+    async fn rebuild_proposal(&self, input: RebuildProposalInput) -> BatcherClientResult<()> {
+        self.with_deadline(self.client.rebuild_proposal(input)).await
+    }
+
+    async fn get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<GetProposalContentResponse> {
+        self.with_deadline(self.client.get_proposal_content(input)).await
+    }
+
+    async fn try_get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<Option<GetProposalContentResponse>> {
+        self.with_deadline(self.client.try_get_proposal_content(input)).await
+    }
+
+    async fn validate_proposal(&self, input: ValidateProposalInput) -> BatcherClientResult<()> {
+        self.with_deadline(self.client.validate_proposal(input)).await
+    }
+
+    async fn send_proposal_content(
+        &self,
+        input: SendProposalContentInput,
+    ) -> BatcherClientResult<SendProposalContentResponse> {
+        self.with_deadline(self.client.send_proposal_content(input)).await
+    }
+
+    async fn start_height(&self, input: StartHeightInput) -> BatcherClientResult<()> {
+        self.with_deadline(self.client.start_height(input)).await
+    }
+
+    async fn decision_reached(
+        &self,
+        input: DecisionReachedInput,
+    ) -> BatcherClientResult<DecisionReachedResponse> {
+        self.with_deadline(self.client.decision_reached(input)).await
+    }
+
+    async fn pre_confirm(&self, input: PreConfirmInput) -> BatcherClientResult<PreConfirmation> {
+        self.with_deadline(self.client.pre_confirm(input)).await
+    }
+
+    async fn summarize_transactions(
+        &self,
+        input: SummarizeTransactionsInput,
+    ) -> BatcherClientResult<Vec<PortableSummary>> {
+        self.with_deadline(self.client.summarize_transactions(input)).await
+    }
+
+    async fn get_proposal_status(
+        &self,
+        input: GetProposalStatusInput,
+    ) -> BatcherClientResult<GetProposalStatusResponse> {
+        self.with_deadline(self.client.get_proposal_status(input)).await
+    }
+
+    async fn list_proposals(&self) -> BatcherClientResult<Vec<(ProposalId, ProposalStatus)>> {
+        self.with_deadline(self.client.list_proposals()).await
+    }
+
+    async fn warm_up(&self, hint: WarmUpHint) -> BatcherClientResult<()> {
+        self.with_deadline(self.client.warm_up(hint)).await
+    }
+}
+
+/// Extends every batcher client with [Self::with_timeout].
+pub trait BatcherClientExt {
+    /// Returns a variant of this client whose requests fail with [BatcherClientError::Timeout]
+    /// if not answered within the given timeout, e.g., for a single long build. This client keeps
+    /// its own timeout.
+    fn with_timeout(&self, timeout: Duration) -> SharedBatcherClient;
+}
+
+impl<T: BatcherClient + Clone + 'static> BatcherClientExt for T {
+    fn with_timeout(&self, timeout: Duration) -> SharedBatcherClient {
+        Arc::new(TimeoutBatcherClient::new(Arc::new(self.clone()), timeout))
+    }
+}
+
+impl BatcherClientExt for SharedBatcherClient {
+    fn with_timeout(&self, timeout: Duration) -> SharedBatcherClient {
+        Arc::new(TimeoutBatcherClient::new(self.clone(), timeout))
+    }
+}
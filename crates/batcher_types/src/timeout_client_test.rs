@@ -0,0 +1,51 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+use starknet_mempool_infra::component_definitions::ComponentRequestAndResponseSender;
+use tokio::sync::mpsc::channel;
+
+use crate::communication::{
+    BatcherClient,
+    BatcherClientError,
+    BatcherRequestAndResponseSender,
+    BatcherResponse,
+    LocalBatcherClient,
+};
+use crate::timeout_client::{BatcherClientExt, TimeoutBatcherClient};
+
+const RESPONSE_DELAY: Duration = Duration::from_millis(100);
+const SHORT_TIMEOUT: Duration = Duration::from_millis(10);
+const LONG_TIMEOUT: Duration = Duration::from_secs(10);
+
+// A client of a batcher that answers every request with an empty proposal list, after a delay.
+fn slow_batcher_client() -> LocalBatcherClient {
+    let (tx_batcher, mut rx_batcher) = channel::<BatcherRequestAndResponseSender>(1);
+    tokio::spawn(async move {
+        while let Some(ComponentRequestAndResponseSender { tx, .. }) = rx_batcher.recv().await {
+            tokio::time::sleep(RESPONSE_DELAY).await;
+            // The client may have timed out and stopped waiting for the response.
+            let _ = tx.send(BatcherResponse::ListProposals(Ok(Vec::new()))).await;
+        }
+    });
+    LocalBatcherClient::new(tx_batcher)
+}
+
+#[tokio::test]
+async fn derived_client_uses_the_new_timeout() {
+    let client = TimeoutBatcherClient::new(Arc::new(slow_batcher_client()), SHORT_TIMEOUT);
+    let derived_client = client.with_timeout(LONG_TIMEOUT);
+
+    assert!(derived_client.list_proposals().await.unwrap().is_empty());
+    assert_eq!(client.timeout(), SHORT_TIMEOUT);
+    assert_matches!(
+        client.list_proposals().await,
+        Err(BatcherClientError::Timeout { timeout }) if timeout == SHORT_TIMEOUT
+    );
+}
+
+#[tokio::test]
+async fn local_client_with_timeout() {
+    let client = slow_batcher_client().with_timeout(SHORT_TIMEOUT);
+    assert_matches!(client.list_proposals().await, Err(BatcherClientError::Timeout { .. }));
+}
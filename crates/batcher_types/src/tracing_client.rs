@@ -0,0 +1,135 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use blockifier::execution::call_info::PortableSummary;
+use tracing::{info_span, Instrument};
+
+use crate::batcher_types::{
+    BuildProposalInput,
+    DecisionReachedInput,
+    DecisionReachedResponse,
+    GetProposalContentInput,
+    GetProposalContentResponse,
+    GetProposalStatusInput,
+    GetProposalStatusResponse,
+    PreConfirmInput,
+    PreConfirmation,
+    ProposalId,
+    ProposalStatus,
+    RebuildProposalInput,
+    SendProposalContentInput,
+    SendProposalContentResponse,
+    StartHeightInput,
+    SummarizeTransactionsInput,
+    ValidateProposalInput,
+    WarmUpHint,
+};
+use crate::communication::{BatcherClient, BatcherClientResult, SharedBatcherClient};
+
+/// A batcher client that runs one in every `sample_every` requests inside a `batcher_request`
+/// span, named after the called method. A rate of 0 disables tracing; the other requests are
+/// forwarded without creating a span at all.
+pub struct TracingBatcherClient {
+    client: SharedBatcherClient,
+    sample_every: usize,
+    n_requests: AtomicUsize,
+}
+
+impl TracingBatcherClient {
+    pub fn new(client: SharedBatcherClient, sample_every: usize) -> Self {
+        Self { client, sample_every, n_requests: AtomicUsize::new(0) }
+    }
+
+    pub fn sample_every(&self) -> usize {
+        self.sample_every
+    }
+
+    fn should_sample(&self) -> bool {
+        if self.sample_every == 0 {
+            return false;
+        }
+        self.n_requests.fetch_add(1, Ordering::Relaxed) % self.sample_every == 0
+    }
+
+    async fn traced<T>(&self, method: &'static str, request: impl Future<Output = T>) -> T {
+        if self.should_sample() {
+            request.instrument(info_span!("batcher_request", method)).await
+        } else {
+            request.await
+        }
+    }
+}
+
+#[async_trait]
+impl BatcherClient for TracingBatcherClient {
+    async fn build_proposal(&self, input: BuildProposalInput) -> BatcherClientResult<()> {
+        self.traced("build_proposal", self.client.build_proposal(input)).await
+    }
+
+    async fn rebuild_proposal(&self, input: RebuildProposalInput) -> BatcherClientResult<()> {
+        self.traced("rebuild_proposal", self.client.rebuild_proposal(input)).await
+    }
+
+    async fn get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<GetProposalContentResponse> {
+        self.traced("get_proposal_content", self.client.get_proposal_content(input)).await
+    }
+
+    async fn try_get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<Option<GetProposalContentResponse>> {
+        self.traced("try_get_proposal_content", self.client.try_get_proposal_content(input)).await
+    }
+
+    async fn validate_proposal(&self, input: ValidateProposalInput) -> BatcherClientResult<()> {
+        self.traced("validate_proposal", self.client.validate_proposal(input)).await
+    }
+
+    async fn send_proposal_content(
+        &self,
+        input: SendProposalContentInput,
+    ) -> BatcherClientResult<SendProposalContentResponse> {
+        self.traced("send_proposal_content", self.client.send_proposal_content(input)).await
+    }
+
+    async fn start_height(&self, input: StartHeightInput) -> BatcherClientResult<()> {
+        self.traced("start_height", self.client.start_height(input)).await
+    }
+
+    async fn decision_reached(
+        &self,
+        input: DecisionReachedInput,
+    ) -> BatcherClientResult<DecisionReachedResponse> {
+        self.traced("decision_reached", self.client.decision_reached(input)).await
+    }
+
+    async fn pre_confirm(&self, input: PreConfirmInput) -> BatcherClientResult<PreConfirmation> {
+        self.traced("pre_confirm", self.client.pre_confirm(input)).await
+    }
+
+    async fn summarize_transactions(
+        &self,
+        input: SummarizeTransactionsInput,
+    ) -> BatcherClientResult<Vec<PortableSummary>> {
+        self.traced("summarize_transactions", self.client.summarize_transactions(input)).await
+    }
+
+    async fn get_proposal_status(
+        &self,
+        input: GetProposalStatusInput,
+    ) -> BatcherClientResult<GetProposalStatusResponse> {
+        self.traced("get_proposal_status", self.client.get_proposal_status(input)).await
+    }
+
+    async fn list_proposals(&self) -> BatcherClientResult<Vec<(ProposalId, ProposalStatus)>> {
+        self.traced("list_proposals", self.client.list_proposals()).await
+    }
+
+    async fn warm_up(&self, hint: WarmUpHint) -> BatcherClientResult<()> {
+        self.traced("warm_up", self.client.warm_up(hint)).await
+    }
+}
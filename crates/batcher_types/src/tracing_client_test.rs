@@ -0,0 +1,46 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::Layer;
+
+use crate::communication::{BatcherClient, MockBatcherClient};
+use crate::tracing_client::TracingBatcherClient;
+
+const N_REQUESTS: usize = 4;
+
+// Counts the spans created while it is the default subscriber.
+struct SpanCounter(Arc<AtomicUsize>);
+
+impl<S: Subscriber> Layer<S> for SpanCounter {
+    fn on_new_span(&self, _attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+async fn n_spans_for_requests(sample_every: usize) -> usize {
+    let mut mock_client = MockBatcherClient::new();
+    mock_client.expect_list_proposals().times(N_REQUESTS).returning(|| Ok(Vec::new()));
+    let client = TracingBatcherClient::new(Arc::new(mock_client), sample_every);
+
+    let n_spans = Arc::new(AtomicUsize::new(0));
+    let subscriber = tracing_subscriber::registry().with(SpanCounter(n_spans.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+    for _ in 0..N_REQUESTS {
+        client.list_proposals().await.unwrap();
+    }
+    n_spans.load(Ordering::Relaxed)
+}
+
+#[tokio::test]
+async fn zero_sampling_rate_creates_no_spans() {
+    assert_eq!(n_spans_for_requests(0).await, 0);
+}
+
+#[tokio::test]
+async fn samples_one_in_every_n_requests() {
+    assert_eq!(n_spans_for_requests(1).await, N_REQUESTS);
+    assert_eq!(n_spans_for_requests(2).await, N_REQUESTS / 2);
+}
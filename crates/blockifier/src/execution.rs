@@ -1,4 +1,6 @@
 pub mod call_info;
+#[cfg(any(test, feature = "arbitrary"))]
+pub mod call_info_arbitrary;
 pub mod common_hints;
 pub mod contract_address;
 pub mod contract_class;
@@ -11,4 +13,5 @@ pub mod execution_utils;
 pub mod hint_code;
 pub mod native;
 pub mod stack_trace;
+pub mod summary_index;
 pub mod syscalls;
@@ -1,19 +1,25 @@
 use std::collections::HashSet;
 use std::iter::Sum;
-use std::ops::Add;
+use std::ops::{Add, AddAssign};
 
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use serde::Serialize;
-use starknet_api::core::{ClassHash, EthAddress};
+use starknet_api::contract_class::EntryPointType;
+use starknet_api::core::{ClassHash, ContractAddress, EthAddress};
 use starknet_api::state::StorageKey;
 use starknet_api::transaction::{EventContent, L2ToL1Payload};
 use starknet_types_core::felt::Felt;
 
 use crate::execution::contract_class::TrackedResource;
 use crate::execution::entry_point::CallEntryPoint;
+#[cfg(feature = "transaction_serde")]
+use crate::execution::errors::DecodeError;
+use crate::execution::errors::{SummaryConsistencyError, SummaryConversionError};
 use crate::state::cached_state::StorageEntry;
 use crate::utils::u64_from_usize;
 
+pub(crate) const FELT_SIZE_IN_BYTES: usize = 32;
+
 #[cfg_attr(feature = "transaction_serde", derive(serde::Deserialize))]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct Retdata(pub Vec<Felt>);
@@ -25,34 +31,30 @@ macro_rules! retdata {
     };
 }
 
-#[cfg_attr(any(test, feature = "testing"), derive(Clone))]
 #[cfg_attr(feature = "transaction_serde", derive(serde::Deserialize))]
-#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct OrderedEvent {
     pub order: usize,
     pub event: EventContent,
 }
 
-#[cfg_attr(any(test, feature = "testing"), derive(Clone))]
 #[cfg_attr(feature = "transaction_serde", derive(serde::Deserialize))]
-#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct MessageToL1 {
     pub to_address: EthAddress,
     pub payload: L2ToL1Payload,
 }
 
-#[cfg_attr(any(test, feature = "testing"), derive(Clone))]
 #[cfg_attr(feature = "transaction_serde", derive(serde::Deserialize))]
-#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct OrderedL2ToL1Message {
     pub order: usize,
     pub message: MessageToL1,
 }
 
 /// Represents the effects of executing a single entry point.
-#[cfg_attr(any(test, feature = "testing"), derive(Clone))]
 #[cfg_attr(feature = "transaction_serde", derive(serde::Deserialize))]
-#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct CallExecution {
     pub retdata: Retdata,
     pub events: Vec<OrderedEvent>,
@@ -69,36 +71,722 @@ pub struct EventSummary {
     pub total_event_data_size: u64,
 }
 
+#[cfg_attr(feature = "transaction_serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct ExecutionSummary {
     pub executed_class_hashes: HashSet<ClassHash>,
     pub visited_storage_entries: HashSet<StorageEntry>,
+    // The subset of the visited storage entries that were written to.
+    pub written_storage_entries: HashSet<StorageEntry>,
     pub l2_to_l1_payload_lengths: Vec<usize>,
     pub event_summary: EventSummary,
+    // The largest number of storage keys accessed by a single call.
+    pub peak_per_call_storage_entries: usize,
+    // The number of constructor calls, and of all other calls.
+    pub constructor_call_count: usize,
+    pub regular_call_count: usize,
+    // The number of events with neither keys nor data.
+    pub empty_event_count: usize,
+    // The lowest and highest orders of the events, if any.
+    pub event_order_range: Option<(usize, usize)>,
+    // The number of calls visited.
+    pub total_calls: usize,
+    // The largest number of data felts in a single event.
+    pub max_single_event_data: usize,
 }
 
 impl Add for ExecutionSummary {
     type Output = Self;
 
     fn add(mut self, other: Self) -> Self {
-        self.executed_class_hashes.extend(other.executed_class_hashes);
-        self.visited_storage_entries.extend(other.visited_storage_entries);
-        self.l2_to_l1_payload_lengths.extend(other.l2_to_l1_payload_lengths);
-        self.event_summary += other.event_summary;
+        self += &other;
         self
     }
 }
 
+impl AddAssign<&ExecutionSummary> for ExecutionSummary {
+    fn add_assign(&mut self, other: &Self) {
+        self.executed_class_hashes.extend(&other.executed_class_hashes);
+        self.visited_storage_entries.extend(&other.visited_storage_entries);
+        self.written_storage_entries.extend(&other.written_storage_entries);
+        self.l2_to_l1_payload_lengths.extend(&other.l2_to_l1_payload_lengths);
+        self.event_summary += other.event_summary.clone();
+        self.peak_per_call_storage_entries =
+            self.peak_per_call_storage_entries.max(other.peak_per_call_storage_entries);
+        self.constructor_call_count += other.constructor_call_count;
+        self.regular_call_count += other.regular_call_count;
+        self.empty_event_count += other.empty_event_count;
+        if let Some((min_order, max_order)) = other.event_order_range {
+            extend_order_range(&mut self.event_order_range, min_order);
+            extend_order_range(&mut self.event_order_range, max_order);
+        }
+        self.total_calls += other.total_calls;
+        self.max_single_event_data = self.max_single_event_data.max(other.max_single_event_data);
+    }
+}
+
+/// Extends the given (inclusive) range to contain the given order.
+fn extend_order_range(order_range: &mut Option<(usize, usize)>, order: usize) {
+    *order_range = Some(match *order_range {
+        Some((min_order, max_order)) => (min_order.min(order), max_order.max(order)),
+        None => (order, order),
+    });
+}
+
 impl Sum for ExecutionSummary {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(ExecutionSummary::default(), |acc, x| acc + x)
     }
 }
 
+impl FromIterator<CallInfo> for ExecutionSummary {
+    fn from_iter<I: IntoIterator<Item = CallInfo>>(iter: I) -> Self {
+        iter.into_iter().map(|call_info| call_info.summarize()).sum()
+    }
+}
+
+macro_rules! impl_assert_matches {
+    ($($field:ident),+) => {
+        /// Compares the summary with the expected one, field by field. On mismatch, returns a
+        /// message listing every differing field with its expected and actual values, e.g., for
+        /// golden tests.
+        pub fn assert_matches(&self, expected: &Self) -> Result<(), String> {
+            // Destructured so that a new field cannot be left out of the comparison.
+            let Self { $($field),+ } = self;
+            let mut mismatches = Vec::new();
+            $(
+                if *$field != expected.$field {
+                    mismatches.push(format!(
+                        "{}: expected {:?}, got {:?}",
+                        stringify!($field),
+                        expected.$field,
+                        $field
+                    ));
+                }
+            )+
+            if mismatches.is_empty() {
+                return Ok(());
+            }
+            Err(format!("Execution summaries differ in:\n{}", mismatches.join("\n")))
+        }
+    };
+}
+
+impl ExecutionSummary {
+    impl_assert_matches!(
+        executed_class_hashes,
+        visited_storage_entries,
+        written_storage_entries,
+        l2_to_l1_payload_lengths,
+        event_summary,
+        peak_per_call_storage_entries,
+        constructor_call_count,
+        regular_call_count,
+        empty_event_count,
+        event_order_range,
+        total_calls,
+        max_single_event_data
+    );
+
+    /// Resets the summary to its default state, keeping the allocated capacity for reuse.
+    pub fn clear(&mut self) {
+        let ExecutionSummary {
+            executed_class_hashes,
+            visited_storage_entries,
+            written_storage_entries,
+            l2_to_l1_payload_lengths,
+            event_summary,
+            peak_per_call_storage_entries,
+            constructor_call_count,
+            regular_call_count,
+            empty_event_count,
+            event_order_range,
+            total_calls,
+            max_single_event_data,
+        } = self;
+        executed_class_hashes.clear();
+        visited_storage_entries.clear();
+        written_storage_entries.clear();
+        l2_to_l1_payload_lengths.clear();
+        *event_summary = EventSummary::default();
+        *peak_per_call_storage_entries = 0;
+        *constructor_call_count = 0;
+        *regular_call_count = 0;
+        *empty_event_count = 0;
+        *event_order_range = None;
+        *total_calls = 0;
+        *max_single_event_data = 0;
+    }
+
+    /// Builds a summary from its core parts, in field order: the executed class hashes, the
+    /// visited storage entries, the payload length of each L2-to-L1 message (in emission order),
+    /// and the event counters. All other fields are left at their defaults (e.g., no written
+    /// storage entries); use [ExecutionSummaryBuilder] to set them.
+    pub fn from_parts(
+        executed_class_hashes: HashSet<ClassHash>,
+        visited_storage_entries: HashSet<StorageEntry>,
+        l2_to_l1_payload_lengths: Vec<usize>,
+        event_summary: EventSummary,
+    ) -> Self {
+        Self {
+            executed_class_hashes,
+            visited_storage_entries,
+            l2_to_l1_payload_lengths,
+            event_summary,
+            ..Default::default()
+        }
+    }
+
+    /// Compares two summaries, treating the L2-to-L1 payload lengths as a multiset (i.e., ignoring
+    /// the order of the messages).
+    pub fn eq_ignoring_message_order(&self, other: &Self) -> bool {
+        let with_sorted_payload_lengths = |summary: &Self| {
+            let mut summary = summary.clone();
+            summary.l2_to_l1_payload_lengths.sort_unstable();
+            summary
+        };
+        with_sorted_payload_lengths(self) == with_sorted_payload_lengths(other)
+    }
+
+    /// Returns the visited storage entries that were only read, never written.
+    pub fn read_only_entries(&self) -> HashSet<StorageEntry> {
+        self.visited_storage_entries.difference(&self.written_storage_entries).copied().collect()
+    }
+
+    /// Returns the visited storage entries sorted by contract address and then by key, e.g., for
+    /// reproducible access lists.
+    pub fn sorted_storage_entries(&self) -> Vec<StorageEntry> {
+        let mut storage_entries: Vec<StorageEntry> =
+            self.visited_storage_entries.iter().copied().collect();
+        storage_entries.sort_unstable();
+        storage_entries
+    }
+
+    /// Returns the total number of felts in the keys and data of all events, which determines the
+    /// events' data-availability gas.
+    pub fn total_event_felts(&self) -> usize {
+        let total_event_felts =
+            self.event_summary.total_event_keys + self.event_summary.total_event_data_size;
+        usize::try_from(total_event_felts).expect("Event felts are counted from usize lengths.")
+    }
+
+    /// Returns the mean number of events emitted per call, or 0 if there were no calls.
+    // The counts are well within f64's exact integer range.
+    #[allow(clippy::as_conversions)]
+    pub fn avg_events_per_call(&self) -> f64 {
+        if self.total_calls == 0 {
+            return 0.0;
+        }
+        self.event_summary.n_events as f64 / self.total_calls as f64
+    }
+
+    /// Returns the data-availability footprint of the summary, in bytes: a felt per written storage
+    /// entry, event felt and message payload felt.
+    pub fn da_size_bytes(&self, felt_size: usize) -> usize {
+        let n_payload_felts: usize = self.l2_to_l1_payload_lengths.iter().sum();
+        (self.written_storage_entries.len() + self.total_event_felts() + n_payload_felts)
+            * felt_size
+    }
+
+    /// Returns the contracts whose storage was visited.
+    pub fn touched_contract_addresses(&self) -> HashSet<ContractAddress> {
+        self.visited_storage_entries.iter().map(|(contract_address, _)| *contract_address).collect()
+    }
+
+    /// Returns a Bloom filter of the [touched contracts](Self::touched_contract_addresses), e.g.,
+    /// for cheaply ruling out conflicts between transactions.
+    pub fn contract_bloom(&self) -> Bloom {
+        let mut bloom = Bloom::default();
+        for contract_address in self.touched_contract_addresses() {
+            bloom.insert(contract_address);
+        }
+        bloom
+    }
+
+    /// Renders the summary's counts and set sizes, one per line, with aligned labels.
+    pub fn report(&self) -> String {
+        let n_payload_felts: usize = self.l2_to_l1_payload_lengths.iter().sum();
+        let rows = [
+            ("classes", u64_from_usize(self.executed_class_hashes.len())),
+            ("storage entries", u64_from_usize(self.visited_storage_entries.len())),
+            ("events", u64_from_usize(self.event_summary.n_events)),
+            ("event keys", self.event_summary.total_event_keys),
+            ("messages", u64_from_usize(self.l2_to_l1_payload_lengths.len())),
+            ("payload bytes", u64_from_usize(n_payload_felts * FELT_SIZE_IN_BYTES)),
+        ];
+        let label_width = rows.iter().map(|(label, _)| label.len() + 1).max().unwrap_or_default();
+        rows.iter()
+            .map(|(label, value)| format!("{:<label_width$} {value}\n", format!("{label}:")))
+            .collect()
+    }
+
+    /// Returns the cost of the summary according to the given weights, split by cost driver.
+    pub fn fee_breakdown(&self, weights: &SummaryWeights) -> FeeBreakdown {
+        let fee_input = FeeInput::from(self);
+        let event_cost = self.event_cost(weights);
+        let message_cost = u128::from(fee_input.n_messages) * weights.message
+            + u128::from(fee_input.n_message_payload_felts) * weights.message_payload_felt;
+        let storage_cost = u128::from(fee_input.n_storage_entries) * weights.storage_entry;
+        let class_cost = u128::from(fee_input.n_class_hashes) * weights.class_hash;
+
+        FeeBreakdown {
+            event_cost,
+            message_cost,
+            storage_cost,
+            class_cost,
+            total: event_cost + message_cost + storage_cost + class_cost,
+        }
+    }
+
+    /// Returns the cost of the summary's events alone (their count, keys and data felts)
+    /// according to the given weights; the event portion of [Self::weighted_cost].
+    pub fn event_cost(&self, weights: &SummaryWeights) -> u128 {
+        let EventSummary { n_events, total_event_keys, total_event_data_size } = self.event_summary;
+        u128::from(u64_from_usize(n_events)) * weights.event
+            + u128::from(total_event_keys) * weights.event_key
+            + u128::from(total_event_data_size) * weights.event_data_felt
+    }
+
+    /// Returns the total cost of the summary according to the given weights.
+    pub fn weighted_cost(&self, weights: &SummaryWeights) -> u128 {
+        self.fee_breakdown(weights).total
+    }
+
+    /// Returns a platform-independent representation of the summary: counters are widened to
+    /// `u64` and sets are sorted, so that equal summaries serialize identically on all nodes.
+    pub fn to_portable(&self) -> PortableSummary {
+        let to_u64s = |counts: &[usize]| counts.iter().copied().map(u64_from_usize).collect();
+        let mut executed_class_hashes: Vec<ClassHash> =
+            self.executed_class_hashes.iter().copied().collect();
+        executed_class_hashes.sort_unstable();
+        let visited_storage_entries = self.sorted_storage_entries();
+        let mut written_storage_entries: Vec<StorageEntry> =
+            self.written_storage_entries.iter().copied().collect();
+        written_storage_entries.sort_unstable();
+
+        PortableSummary {
+            executed_class_hashes,
+            visited_storage_entries,
+            written_storage_entries,
+            l2_to_l1_payload_lengths: to_u64s(&self.l2_to_l1_payload_lengths),
+            n_events: u64_from_usize(self.event_summary.n_events),
+            total_event_keys: self.event_summary.total_event_keys,
+            total_event_data_size: self.event_summary.total_event_data_size,
+            peak_per_call_storage_entries: u64_from_usize(self.peak_per_call_storage_entries),
+            constructor_call_count: u64_from_usize(self.constructor_call_count),
+            regular_call_count: u64_from_usize(self.regular_call_count),
+            empty_event_count: u64_from_usize(self.empty_event_count),
+            event_order_range: self.event_order_range.map(|(min_order, max_order)| {
+                (u64_from_usize(min_order), u64_from_usize(max_order))
+            }),
+            total_calls: u64_from_usize(self.total_calls),
+            max_single_event_data: u64_from_usize(self.max_single_event_data),
+        }
+    }
+
+    /// Returns a flat record of the summary's scalars, with each collection replaced by its size.
+    pub fn to_record(&self) -> SummaryRecord {
+        SummaryRecord {
+            n_executed_classes: u64_from_usize(self.executed_class_hashes.len()),
+            n_visited_storage_entries: u64_from_usize(self.visited_storage_entries.len()),
+            n_messages: u64_from_usize(self.l2_to_l1_payload_lengths.len()),
+            total_payload_length: u64_from_usize(self.l2_to_l1_payload_lengths.iter().sum()),
+            n_events: u64_from_usize(self.event_summary.n_events),
+            total_event_keys: self.event_summary.total_event_keys,
+            total_event_data_size: self.event_summary.total_event_data_size,
+            peak_per_call_storage_entries: u64_from_usize(self.peak_per_call_storage_entries),
+            constructor_call_count: u64_from_usize(self.constructor_call_count),
+            regular_call_count: u64_from_usize(self.regular_call_count),
+            empty_event_count: u64_from_usize(self.empty_event_count),
+            min_event_order: self.event_order_range.map(|(min_order, _)| u64_from_usize(min_order)),
+            max_event_order: self.event_order_range.map(|(_, max_order)| u64_from_usize(max_order)),
+            total_calls: u64_from_usize(self.total_calls),
+            max_single_event_data: u64_from_usize(self.max_single_event_data),
+        }
+    }
+}
+
+/// The number of bits in a [Bloom] filter.
+pub const BLOOM_SIZE_IN_BITS: usize = 2048;
+// The number of bits set for each inserted contract.
+const BLOOM_N_HASHES: u64 = 3;
+
+/// A fixed-size Bloom filter of contract addresses. Filters sharing an inserted contract always
+/// intersect, but filters of disjoint contracts may intersect as well (false positives).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Bloom {
+    bits: [u64; BLOOM_SIZE_IN_BITS / 64],
+}
+
+impl Bloom {
+    pub fn insert(&mut self, contract_address: ContractAddress) {
+        for bit in Self::bit_indices(contract_address) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Returns false if the contract was surely not inserted.
+    pub fn may_contain(&self, contract_address: ContractAddress) -> bool {
+        Self::bit_indices(contract_address).all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Returns false if the filters surely have no contract in common.
+    pub fn may_intersect(&self, other: &Bloom) -> bool {
+        self.bits.iter().zip(other.bits.iter()).any(|(bits, other_bits)| bits & other_bits != 0)
+    }
+
+    // Derives the bits of the contract by double hashing a SplitMix64 digest of its address.
+    fn bit_indices(contract_address: ContractAddress) -> impl Iterator<Item = usize> {
+        let digest =
+            contract_address.0.key().to_bytes_be().chunks_exact(8).fold(0, |digest, word| {
+                let word = u64::from_be_bytes(word.try_into().expect("Words are 8 bytes."));
+                splitmix64(digest ^ word)
+            });
+        let (first_hash, second_hash) = (digest & 0xffff_ffff, digest >> 32);
+        let n_bits = u64_from_usize(BLOOM_SIZE_IN_BITS);
+        (0..BLOOM_N_HASHES).map(move |i| {
+            let bit = first_hash.wrapping_add(i.wrapping_mul(second_hash)) % n_bits;
+            usize::try_from(bit).expect("Bit indices are below the filter size.")
+        })
+    }
+}
+
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Per-unit weights used to price the resources recorded in an [ExecutionSummary].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SummaryWeights {
+    pub event: u128,
+    pub event_key: u128,
+    pub event_data_felt: u128,
+    pub message: u128,
+    pub message_payload_felt: u128,
+    pub storage_entry: u128,
+    pub class_hash: u128,
+}
+
+/// The resource counts of an [ExecutionSummary] that drive its fee, one per [SummaryWeights]
+/// field.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FeeInput {
+    pub n_events: u64,
+    pub n_event_keys: u64,
+    pub n_event_data_felts: u64,
+    pub n_messages: u64,
+    pub n_message_payload_felts: u64,
+    pub n_storage_entries: u64,
+    pub n_class_hashes: u64,
+}
+
+impl From<&ExecutionSummary> for FeeInput {
+    fn from(summary: &ExecutionSummary) -> Self {
+        Self {
+            n_events: u64_from_usize(summary.event_summary.n_events),
+            n_event_keys: summary.event_summary.total_event_keys,
+            n_event_data_felts: summary.event_summary.total_event_data_size,
+            n_messages: u64_from_usize(summary.l2_to_l1_payload_lengths.len()),
+            n_message_payload_felts: u64_from_usize(summary.l2_to_l1_payload_lengths.iter().sum()),
+            n_storage_entries: u64_from_usize(summary.visited_storage_entries.len()),
+            n_class_hashes: u64_from_usize(summary.executed_class_hashes.len()),
+        }
+    }
+}
+
+/// The weighted cost of an [ExecutionSummary], split by cost driver.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FeeBreakdown {
+    pub event_cost: u128,
+    pub message_cost: u128,
+    pub storage_cost: u128,
+    pub class_cost: u128,
+    pub total: u128,
+}
+
+/// A platform-independent form of an [ExecutionSummary], suitable for cross-node comparison and
+/// hashing. See [ExecutionSummary::to_portable].
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, Serialize)]
+pub struct PortableSummary {
+    pub executed_class_hashes: Vec<ClassHash>,
+    pub visited_storage_entries: Vec<StorageEntry>,
+    pub written_storage_entries: Vec<StorageEntry>,
+    pub l2_to_l1_payload_lengths: Vec<u64>,
+    pub n_events: u64,
+    pub total_event_keys: u64,
+    pub total_event_data_size: u64,
+    pub peak_per_call_storage_entries: u64,
+    pub constructor_call_count: u64,
+    pub regular_call_count: u64,
+    pub empty_event_count: u64,
+    pub event_order_range: Option<(u64, u64)>,
+    pub total_calls: u64,
+    pub max_single_event_data: u64,
+}
+
+/// A flat form of an [ExecutionSummary] with scalar fields only, e.g., for a CSV row. See
+/// [ExecutionSummary::to_record].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct SummaryRecord {
+    pub n_executed_classes: u64,
+    pub n_visited_storage_entries: u64,
+    pub n_messages: u64,
+    pub total_payload_length: u64,
+    pub n_events: u64,
+    pub total_event_keys: u64,
+    pub total_event_data_size: u64,
+    pub peak_per_call_storage_entries: u64,
+    pub constructor_call_count: u64,
+    pub regular_call_count: u64,
+    pub empty_event_count: u64,
+    pub min_event_order: Option<u64>,
+    pub max_event_order: Option<u64>,
+    pub total_calls: u64,
+    pub max_single_event_data: u64,
+}
+
+/// A protobuf-friendly mirror of an [ExecutionSummary]: all fields are (repeated) scalars, and
+/// felt-like values are big-endian byte arrays. Storage entries are split into parallel repeated
+/// fields.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProtoExecutionSummary {
+    pub executed_class_hashes: Vec<[u8; 32]>,
+    pub visited_storage_addresses: Vec<[u8; 32]>,
+    pub visited_storage_keys: Vec<[u8; 32]>,
+    pub written_storage_addresses: Vec<[u8; 32]>,
+    pub written_storage_keys: Vec<[u8; 32]>,
+    pub l2_to_l1_payload_lengths: Vec<u64>,
+    pub n_events: u64,
+    pub total_event_keys: u64,
+    pub total_event_data_size: u64,
+    pub peak_per_call_storage_entries: u64,
+    pub constructor_call_count: u64,
+    pub regular_call_count: u64,
+    pub empty_event_count: u64,
+    pub min_event_order: Option<u64>,
+    pub max_event_order: Option<u64>,
+    pub total_calls: u64,
+    pub max_single_event_data: u64,
+}
+
+impl From<ExecutionSummary> for ProtoExecutionSummary {
+    fn from(summary: ExecutionSummary) -> Self {
+        let to_u64s = |counts: Vec<usize>| counts.into_iter().map(u64_from_usize).collect();
+        let split_storage_entries =
+            |storage_entries: HashSet<StorageEntry>| -> (Vec<[u8; 32]>, Vec<[u8; 32]>) {
+                storage_entries
+                    .into_iter()
+                    .map(|(address, key)| {
+                        (address.0.key().to_bytes_be(), key.0.key().to_bytes_be())
+                    })
+                    .unzip()
+            };
+        let (visited_storage_addresses, visited_storage_keys) =
+            split_storage_entries(summary.visited_storage_entries);
+        let (written_storage_addresses, written_storage_keys) =
+            split_storage_entries(summary.written_storage_entries);
+
+        Self {
+            executed_class_hashes: summary
+                .executed_class_hashes
+                .into_iter()
+                .map(|class_hash| class_hash.0.to_bytes_be())
+                .collect(),
+            visited_storage_addresses,
+            visited_storage_keys,
+            written_storage_addresses,
+            written_storage_keys,
+            l2_to_l1_payload_lengths: to_u64s(summary.l2_to_l1_payload_lengths),
+            n_events: u64_from_usize(summary.event_summary.n_events),
+            total_event_keys: summary.event_summary.total_event_keys,
+            total_event_data_size: summary.event_summary.total_event_data_size,
+            peak_per_call_storage_entries: u64_from_usize(summary.peak_per_call_storage_entries),
+            constructor_call_count: u64_from_usize(summary.constructor_call_count),
+            regular_call_count: u64_from_usize(summary.regular_call_count),
+            empty_event_count: u64_from_usize(summary.empty_event_count),
+            min_event_order: summary
+                .event_order_range
+                .map(|(min_order, _)| u64_from_usize(min_order)),
+            max_event_order: summary
+                .event_order_range
+                .map(|(_, max_order)| u64_from_usize(max_order)),
+            total_calls: u64_from_usize(summary.total_calls),
+            max_single_event_data: u64_from_usize(summary.max_single_event_data),
+        }
+    }
+}
+
+impl TryFrom<ProtoExecutionSummary> for ExecutionSummary {
+    type Error = SummaryConversionError;
+
+    fn try_from(proto: ProtoExecutionSummary) -> Result<Self, Self::Error> {
+        let to_usize =
+            |count: u64| usize::try_from(count).map_err(|_| Self::Error::CounterOutOfRange(count));
+        let to_usizes = |counts: Vec<u64>| -> Result<Vec<usize>, Self::Error> {
+            counts.into_iter().map(to_usize).collect()
+        };
+        let to_felt = |bytes: [u8; 32]| Felt::from_bytes_be(&bytes);
+
+        if proto.visited_storage_addresses.len() != proto.visited_storage_keys.len() {
+            return Err(Self::Error::MismatchedLengths {
+                fields: "visited_storage_addresses, visited_storage_keys",
+            });
+        }
+        if proto.written_storage_addresses.len() != proto.written_storage_keys.len() {
+            return Err(Self::Error::MismatchedLengths {
+                fields: "written_storage_addresses, written_storage_keys",
+            });
+        }
+
+        let join_storage_entries = |addresses: Vec<[u8; 32]>, keys: Vec<[u8; 32]>| {
+            addresses
+                .into_iter()
+                .zip(keys)
+                .map(|(address, key)| -> Result<StorageEntry, Self::Error> {
+                    Ok((
+                        ContractAddress::try_from(to_felt(address))?,
+                        StorageKey::try_from(to_felt(key))?,
+                    ))
+                })
+                .collect::<Result<HashSet<_>, _>>()
+        };
+        let visited_storage_entries =
+            join_storage_entries(proto.visited_storage_addresses, proto.visited_storage_keys)?;
+        let written_storage_entries =
+            join_storage_entries(proto.written_storage_addresses, proto.written_storage_keys)?;
+
+        let event_order_range = match (proto.min_event_order, proto.max_event_order) {
+            (Some(min_order), Some(max_order)) if min_order <= max_order => {
+                Some((to_usize(min_order)?, to_usize(max_order)?))
+            }
+            (None, None) => None,
+            (min_event_order, max_event_order) => {
+                return Err(Self::Error::InconsistentEventOrderRange {
+                    min_event_order,
+                    max_event_order,
+                });
+            }
+        };
+
+        Ok(Self {
+            executed_class_hashes: proto
+                .executed_class_hashes
+                .into_iter()
+                .map(|class_hash| ClassHash(to_felt(class_hash)))
+                .collect(),
+            visited_storage_entries,
+            written_storage_entries,
+            l2_to_l1_payload_lengths: to_usizes(proto.l2_to_l1_payload_lengths)?,
+            event_summary: EventSummary {
+                n_events: to_usize(proto.n_events)?,
+                total_event_keys: proto.total_event_keys,
+                total_event_data_size: proto.total_event_data_size,
+            },
+            peak_per_call_storage_entries: to_usize(proto.peak_per_call_storage_entries)?,
+            constructor_call_count: to_usize(proto.constructor_call_count)?,
+            regular_call_count: to_usize(proto.regular_call_count)?,
+            empty_event_count: to_usize(proto.empty_event_count)?,
+            event_order_range,
+            total_calls: to_usize(proto.total_calls)?,
+            max_single_event_data: to_usize(proto.max_single_event_data)?,
+        })
+    }
+}
+
+/// Builds an [ExecutionSummary], e.g., an expected summary in a test, checking on [Self::build]
+/// that its fields are consistent with each other. Fields not set are left at their defaults.
+#[derive(Debug, Default)]
+pub struct ExecutionSummaryBuilder {
+    summary: ExecutionSummary,
+    // The expected number of messages, if set; checked against the payload lengths.
+    n_messages: Option<usize>,
+}
+
+impl ExecutionSummaryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn executed_class_hashes(mut self, executed_class_hashes: HashSet<ClassHash>) -> Self {
+        self.summary.executed_class_hashes = executed_class_hashes;
+        self
+    }
+
+    pub fn visited_storage_entries(
+        mut self,
+        visited_storage_entries: HashSet<StorageEntry>,
+    ) -> Self {
+        self.summary.visited_storage_entries = visited_storage_entries;
+        self
+    }
+
+    pub fn l2_to_l1_payload_lengths(mut self, l2_to_l1_payload_lengths: Vec<usize>) -> Self {
+        self.summary.l2_to_l1_payload_lengths = l2_to_l1_payload_lengths;
+        self
+    }
+
+    /// Sets the expected number of messages, which is not a field of the summary.
+    pub fn n_messages(mut self, n_messages: usize) -> Self {
+        self.n_messages = Some(n_messages);
+        self
+    }
+
+    pub fn event_summary(mut self, event_summary: EventSummary) -> Self {
+        self.summary.event_summary = event_summary;
+        self
+    }
+
+    pub fn empty_event_count(mut self, empty_event_count: usize) -> Self {
+        self.summary.empty_event_count = empty_event_count;
+        self
+    }
+
+    pub fn max_single_event_data(mut self, max_single_event_data: usize) -> Self {
+        self.summary.max_single_event_data = max_single_event_data;
+        self
+    }
+
+    /// Returns the summary, or the first inconsistency found among:
+    /// - the expected number of messages vs. the number of payload lengths;
+    /// - the empty events vs. the number of events;
+    /// - the largest event data vs. the total event data.
+    pub fn build(self) -> Result<ExecutionSummary, SummaryConsistencyError> {
+        let summary = self.summary;
+        let n_payload_lengths = summary.l2_to_l1_payload_lengths.len();
+        if let Some(n_messages) = self.n_messages {
+            if n_messages != n_payload_lengths {
+                return Err(SummaryConsistencyError::MessageCountMismatch {
+                    n_messages,
+                    n_payload_lengths,
+                });
+            }
+        }
+
+        let n_events = summary.event_summary.n_events;
+        if summary.empty_event_count > n_events {
+            return Err(SummaryConsistencyError::TooManyEmptyEvents {
+                empty_event_count: summary.empty_event_count,
+                n_events,
+            });
+        }
+        let total_event_data_size = summary.event_summary.total_event_data_size;
+        if u64_from_usize(summary.max_single_event_data) > total_event_data_size {
+            return Err(SummaryConsistencyError::EventDataSizeMismatch {
+                max_single_event_data: summary.max_single_event_data,
+                total_event_data_size,
+            });
+        }
+
+        Ok(summary)
+    }
+}
+
 /// Represents the full effects of executing an entry point, including the inner calls it invoked.
-#[cfg_attr(any(test, feature = "testing"), derive(Clone))]
 #[cfg_attr(feature = "transaction_serde", derive(serde::Deserialize))]
-#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct CallInfo {
     pub call: CallEntryPoint,
     pub execution: CallExecution,
@@ -109,6 +797,122 @@ pub struct CallInfo {
     // Additional information gathered during execution.
     pub storage_read_values: Vec<Felt>,
     pub accessed_storage_keys: HashSet<StorageKey>,
+    // The subsets of the accessed storage keys that were read from, and written to; a key may be
+    // in both.
+    pub read_storage_keys: HashSet<StorageKey>,
+    pub written_storage_keys: HashSet<StorageKey>,
+}
+
+/// Aggregates data over a call tree, visiting its calls one at a time (see
+/// [crate::transaction::objects::TransactionExecutionInfo::accumulate]).
+pub trait SummaryAccumulator {
+    /// Visits a single call, at the given depth of its call tree; inner calls are visited
+    /// separately.
+    fn visit_call(&mut self, call_info: &CallInfo, depth: usize);
+}
+
+impl SummaryAccumulator for ExecutionSummary {
+    fn visit_call(&mut self, call_info: &CallInfo, _depth: usize) {
+        self.visit_call_with_revert(call_info, false);
+    }
+}
+
+impl ExecutionSummary {
+    /// Visits a single call like [SummaryAccumulator::visit_call]. If the call is `reverted`, its
+    /// storage writes, events and messages are rolled back on-chain, so only its storage reads are
+    /// counted.
+    pub(crate) fn visit_call_with_revert(&mut self, call_info: &CallInfo, reverted: bool) {
+        let class_hash =
+            call_info.call.class_hash.expect("Class hash must be set after execution.");
+        self.visit_call_effects(call_info, Some(class_hash), reverted);
+    }
+
+    /// Visits a single call like [SummaryAccumulator::visit_call], but tolerates a malformed call
+    /// without a class hash: all its other effects are still counted.
+    pub(crate) fn visit_call_lossy(&mut self, call_info: &CallInfo) {
+        self.visit_call_effects(call_info, call_info.call.class_hash, false);
+    }
+
+    // Visits a single call, attributed to the given class hash, if any; see
+    // [Self::visit_call_with_revert].
+    fn visit_call_effects(
+        &mut self,
+        call_info: &CallInfo,
+        class_hash: Option<ClassHash>,
+        reverted: bool,
+    ) {
+        let ExecutionSummary {
+            executed_class_hashes,
+            visited_storage_entries,
+            written_storage_entries,
+            l2_to_l1_payload_lengths,
+            event_summary,
+            peak_per_call_storage_entries,
+            constructor_call_count,
+            regular_call_count,
+            empty_event_count,
+            event_order_range,
+            total_calls,
+            max_single_event_data,
+        } = self;
+
+        *total_calls += 1;
+
+        // Class hashes.
+        if let Some(class_hash) = class_hash {
+            executed_class_hashes.insert(class_hash);
+        }
+        match call_info.call.entry_point_type {
+            EntryPointType::Constructor => *constructor_call_count += 1,
+            EntryPointType::External | EntryPointType::L1Handler => *regular_call_count += 1,
+        }
+
+        // Storage entries; a reverted call's write-only keys are rolled back, its reads are not.
+        let call_storage_keys = call_info.accessed_storage_keys.iter().filter(|storage_key| {
+            !reverted
+                || call_info.read_storage_keys.contains(*storage_key)
+                || !call_info.written_storage_keys.contains(*storage_key)
+        });
+        let mut n_call_storage_keys = 0;
+        for storage_key in call_storage_keys {
+            visited_storage_entries.insert((call_info.call.storage_address, *storage_key));
+            n_call_storage_keys += 1;
+        }
+        *peak_per_call_storage_entries = (*peak_per_call_storage_entries).max(n_call_storage_keys);
+        if !reverted {
+            written_storage_entries.extend(
+                call_info
+                    .written_storage_keys
+                    .iter()
+                    .map(|storage_key| (call_info.call.storage_address, *storage_key)),
+            );
+        }
+
+        let (messages, events) = if reverted {
+            (&[][..], &[][..])
+        } else {
+            (&call_info.execution.l2_to_l1_messages[..], &call_info.execution.events[..])
+        };
+
+        // Messages.
+        l2_to_l1_payload_lengths
+            .extend(messages.iter().map(|message| message.message.payload.0.len()));
+
+        // Events.
+        event_summary.n_events += events.len();
+        for OrderedEvent { order, event } in events.iter() {
+            extend_order_range(event_order_range, *order);
+            // TODO(barak: 18/03/2024): Once we start charging per byte
+            // change to num_bytes_keys
+            // and num_bytes_data.
+            event_summary.total_event_data_size += u64_from_usize(event.data.0.len());
+            event_summary.total_event_keys += u64_from_usize(event.keys.len());
+            if event.keys.is_empty() && event.data.0.is_empty() {
+                *empty_event_count += 1;
+            }
+            *max_single_event_data = (*max_single_event_data).max(event.data.0.len());
+        }
+    }
 }
 
 impl CallInfo {
@@ -117,51 +921,120 @@ impl CallInfo {
         CallInfoIter { call_infos }
     }
 
+    /// Returns a pre-order iterator over the call tree, yielding each call with its depth (the
+    /// root is at depth 0).
+    pub fn iter_with_depth(&self) -> CallInfoWithDepthIter<'_> {
+        let call_infos = vec![(self, 0)];
+        CallInfoWithDepthIter { call_infos }
+    }
+
     pub fn summarize(&self) -> ExecutionSummary {
-        let mut executed_class_hashes: HashSet<ClassHash> = HashSet::new();
-        let mut visited_storage_entries: HashSet<StorageEntry> = HashSet::new();
-        let mut event_summary = EventSummary::default();
-        let mut l2_to_l1_payload_lengths = Vec::new();
-
-        for call_info in self.iter() {
-            // Class hashes.
-            let class_hash =
-                call_info.call.class_hash.expect("Class hash must be set after execution.");
-            executed_class_hashes.insert(class_hash);
+        Self::summarize_calls(self.iter_with_depth())
+    }
 
-            // Storage entries.
-            let call_storage_entries = call_info
-                .accessed_storage_keys
-                .iter()
-                .map(|storage_key| (call_info.call.storage_address, *storage_key));
-            visited_storage_entries.extend(call_storage_entries);
+    /// Summarizes the given calls, each with its depth in its call tree.
+    pub(crate) fn summarize_calls<'a>(
+        calls: impl Iterator<Item = (&'a CallInfo, usize)>,
+    ) -> ExecutionSummary {
+        let mut summary = ExecutionSummary::default();
+        Self::summarize_calls_into(calls, &mut summary);
+        summary
+    }
 
-            // Messages.
-            l2_to_l1_payload_lengths.extend(
-                call_info
-                    .execution
-                    .l2_to_l1_messages
-                    .iter()
-                    .map(|message| message.message.payload.0.len()),
-            );
+    /// Accumulates the summary of the given calls, each with its depth in its call tree, into
+    /// `out`.
+    pub(crate) fn summarize_calls_into<'a>(
+        calls: impl Iterator<Item = (&'a CallInfo, usize)>,
+        out: &mut ExecutionSummary,
+    ) {
+        for (call_info, depth) in calls {
+            out.visit_call(call_info, depth);
+        }
+    }
 
-            // Events.
-            event_summary.n_events += call_info.execution.events.len();
-            for OrderedEvent { event, .. } in call_info.execution.events.iter() {
-                // TODO(barak: 18/03/2024): Once we start charging per byte
-                // change to num_bytes_keys
-                // and num_bytes_data.
-                event_summary.total_event_data_size += u64_from_usize(event.data.0.len());
-                event_summary.total_event_keys += u64_from_usize(event.keys.len());
-            }
+    /// Renders the call tree, one call (its class hash) per line, indented by depth. Inner calls
+    /// deeper than `max_depth` are elided with a `... (N more)` marker counting the elided calls,
+    /// and at most `max_children` inner calls are rendered per call, followed by a marker counting
+    /// the remaining ones.
+    pub fn tree_display_bounded(&self, max_depth: usize, max_children: usize) -> String {
+        let mut output = String::new();
+        self.write_tree_bounded(&mut output, 0, max_depth, max_children);
+        output
+    }
+
+    fn write_tree_bounded(
+        &self,
+        output: &mut String,
+        depth: usize,
+        max_depth: usize,
+        max_children: usize,
+    ) {
+        let indent = "  ".repeat(depth);
+        match self.call.class_hash {
+            Some(class_hash) => output.push_str(&format!("{indent}{:#x}\n", class_hash.0)),
+            None => output.push_str(&format!("{indent}<unknown class>\n")),
+        }
+        if self.inner_calls.is_empty() {
+            return;
         }
 
-        ExecutionSummary {
-            executed_class_hashes,
-            visited_storage_entries,
-            l2_to_l1_payload_lengths,
-            event_summary,
+        if depth >= max_depth {
+            let n_elided_calls = self.iter().count() - 1;
+            output.push_str(&format!("{indent}  ... ({n_elided_calls} more)\n"));
+            return;
+        }
+        for inner_call in self.inner_calls.iter().take(max_children) {
+            inner_call.write_tree_bounded(output, depth + 1, max_depth, max_children);
         }
+        if self.inner_calls.len() > max_children {
+            let n_elided_children = self.inner_calls.len() - max_children;
+            output.push_str(&format!("{indent}  ... ({n_elided_children} more)\n"));
+        }
+    }
+
+    /// Compares the call tree with another one, node by node in pre-order, and returns the
+    /// differences found at the first node where they diverge (empty if the trees agree on every
+    /// node). Only the class hash, event count and number of inner calls of a node are compared;
+    /// the inner calls of a differing node are not.
+    pub fn structural_diff(&self, other: &CallInfo) -> Vec<TreeDiff> {
+        self.structural_diff_at(other, &mut Vec::new())
+    }
+
+    fn structural_diff_at(&self, other: &CallInfo, path: &mut Vec<usize>) -> Vec<TreeDiff> {
+        let mut kinds = Vec::new();
+        if self.call.class_hash != other.call.class_hash {
+            kinds.push(TreeDiffKind::ClassHash {
+                left: self.call.class_hash,
+                right: other.call.class_hash,
+            });
+        }
+        if self.execution.events.len() != other.execution.events.len() {
+            kinds.push(TreeDiffKind::EventCount {
+                left: self.execution.events.len(),
+                right: other.execution.events.len(),
+            });
+        }
+        if self.inner_calls.len() != other.inner_calls.len() {
+            kinds.push(TreeDiffKind::ChildrenCount {
+                left: self.inner_calls.len(),
+                right: other.inner_calls.len(),
+            });
+        }
+        if !kinds.is_empty() {
+            return kinds.into_iter().map(|kind| TreeDiff { path: path.clone(), kind }).collect();
+        }
+
+        for (index, (inner_call, other_inner_call)) in
+            self.inner_calls.iter().zip(&other.inner_calls).enumerate()
+        {
+            path.push(index);
+            let diffs = inner_call.structural_diff_at(other_inner_call, path);
+            if !diffs.is_empty() {
+                return diffs;
+            }
+            path.pop();
+        }
+        Vec::new()
     }
 
     pub fn summarize_many<'a>(call_infos: impl Iterator<Item = &'a CallInfo>) -> ExecutionSummary {
@@ -169,6 +1042,50 @@ impl CallInfo {
     }
 }
 
+/// The version of the binary encoding of call trees (see [CallInfo::encode]). Bump on any change
+/// to the encoded types, so that stale caches are rejected rather than misread.
+#[cfg(feature = "transaction_serde")]
+pub const CALL_INFO_ENCODING_VERSION: u8 = 3;
+
+#[cfg(feature = "transaction_serde")]
+impl CallInfo {
+    /// Encodes the call tree in a compact binary format, prefixed by
+    /// [CALL_INFO_ENCODING_VERSION], e.g., for on-disk trace caches.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = vec![CALL_INFO_ENCODING_VERSION];
+        bincode::serialize_into(&mut bytes, self)
+            .expect("Encoding a call info into memory should not fail.");
+        bytes
+    }
+
+    /// Decodes a call tree encoded by [Self::encode], rejecting other encoding versions.
+    pub fn decode(bytes: &[u8]) -> Result<CallInfo, DecodeError> {
+        let (&version, encoded_call_info) =
+            bytes.split_first().ok_or(DecodeError::MissingVersion)?;
+        if version != CALL_INFO_ENCODING_VERSION {
+            return Err(DecodeError::UnsupportedVersion { version });
+        }
+        Ok(bincode::deserialize(encoded_call_info)?)
+    }
+}
+
+/// A difference between two call trees at a single node, found by [CallInfo::structural_diff].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreeDiff {
+    // The indices of the inner calls leading from the roots to the node; empty for the roots.
+    pub path: Vec<usize>,
+    pub kind: TreeDiffKind,
+}
+
+/// What differs between two call tree nodes; `left` is the value of the tree the diff was taken
+/// on, `right` of the tree it was compared with.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TreeDiffKind {
+    ClassHash { left: Option<ClassHash>, right: Option<ClassHash> },
+    EventCount { left: usize, right: usize },
+    ChildrenCount { left: usize, right: usize },
+}
+
 pub struct CallInfoIter<'a> {
     call_infos: Vec<&'a CallInfo>,
 }
@@ -184,3 +1101,20 @@ impl<'a> Iterator for CallInfoIter<'a> {
         Some(call_info)
     }
 }
+
+pub struct CallInfoWithDepthIter<'a> {
+    call_infos: Vec<(&'a CallInfo, usize)>,
+}
+
+impl<'a> Iterator for CallInfoWithDepthIter<'a> {
+    type Item = (&'a CallInfo, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (call_info, depth) = self.call_infos.pop()?;
+
+        // Push order is right to left.
+        self.call_infos
+            .extend(call_info.inner_calls.iter().rev().map(|inner_call| (inner_call, depth + 1)));
+        Some((call_info, depth))
+    }
+}
@@ -0,0 +1,138 @@
+//! [Arbitrary] implementations for call trees, e.g., for property-testing the summarizers. The
+//! generated trees are well-formed (every call has a class hash, event orders are increasing
+//! within a call, every accessed storage key was read from, written to, or both) and bounded in
+//! depth and width. Values are drawn from small ranges, so that
+//! class hashes, contracts and storage keys repeat across calls.
+
+use std::collections::HashSet;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use starknet_api::contract_class::EntryPointType;
+use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, EthAddress};
+use starknet_api::state::StorageKey;
+use starknet_api::transaction::{EventContent, EventData, EventKey, L2ToL1Payload};
+use starknet_types_core::felt::Felt;
+
+use crate::execution::call_info::{
+    CallExecution,
+    CallInfo,
+    MessageToL1,
+    OrderedEvent,
+    OrderedL2ToL1Message,
+};
+use crate::execution::entry_point::CallEntryPoint;
+
+pub const MAX_ARBITRARY_CALL_DEPTH: usize = 4;
+pub const MAX_ARBITRARY_INNER_CALLS: usize = 4;
+const MAX_ARBITRARY_ITEMS_PER_CALL: usize = 4;
+const MAX_ARBITRARY_FELTS_PER_ITEM: usize = 3;
+const MAX_ARBITRARY_VALUE: u8 = 15;
+
+fn small_felt(u: &mut Unstructured<'_>) -> Result<Felt> {
+    Ok(Felt::from(u.int_in_range(0..=MAX_ARBITRARY_VALUE)?))
+}
+
+fn small_felts(u: &mut Unstructured<'_>) -> Result<Vec<Felt>> {
+    let n_felts = u.int_in_range(0..=MAX_ARBITRARY_FELTS_PER_ITEM)?;
+    (0..n_felts).map(|_| small_felt(u)).collect()
+}
+
+fn n_items(u: &mut Unstructured<'_>) -> Result<usize> {
+    u.int_in_range(0..=MAX_ARBITRARY_ITEMS_PER_CALL)
+}
+
+fn arbitrary_event_content(u: &mut Unstructured<'_>) -> Result<EventContent> {
+    let keys = small_felts(u)?.into_iter().map(EventKey).collect();
+    Ok(EventContent { keys, data: EventData(small_felts(u)?) })
+}
+
+fn arbitrary_message(u: &mut Unstructured<'_>) -> Result<MessageToL1> {
+    Ok(MessageToL1 { to_address: EthAddress::default(), payload: L2ToL1Payload(small_felts(u)?) })
+}
+
+impl<'a> Arbitrary<'a> for OrderedEvent {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self { order: u.arbitrary()?, event: arbitrary_event_content(u)? })
+    }
+}
+
+impl<'a> Arbitrary<'a> for OrderedL2ToL1Message {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self { order: u.arbitrary()?, message: arbitrary_message(u)? })
+    }
+}
+
+impl<'a> Arbitrary<'a> for CallExecution {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // Orders are increasing within a call.
+        let n_events = n_items(u)?;
+        let events = (0..n_events)
+            .map(|order| -> Result<OrderedEvent> {
+                Ok(OrderedEvent { order, event: arbitrary_event_content(u)? })
+            })
+            .collect::<Result<_>>()?;
+        let n_messages = n_items(u)?;
+        let l2_to_l1_messages = (0..n_messages)
+            .map(|order| -> Result<OrderedL2ToL1Message> {
+                Ok(OrderedL2ToL1Message { order, message: arbitrary_message(u)? })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { events, l2_to_l1_messages, ..Default::default() })
+    }
+}
+
+impl<'a> Arbitrary<'a> for CallInfo {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        arbitrary_call_info(u, MAX_ARBITRARY_CALL_DEPTH)
+    }
+}
+
+// Generates a call tree with at most `depth_budget` levels below the root.
+fn arbitrary_call_info(u: &mut Unstructured<'_>, depth_budget: usize) -> Result<CallInfo> {
+    let entry_point_type = *u.choose(&[
+        EntryPointType::Constructor,
+        EntryPointType::External,
+        EntryPointType::L1Handler,
+    ])?;
+    let call = CallEntryPoint {
+        class_hash: Some(ClassHash(small_felt(u)?)),
+        entry_point_selector: EntryPointSelector(small_felt(u)?),
+        entry_point_type,
+        storage_address: ContractAddress::from(u128::from(
+            u.int_in_range(0..=MAX_ARBITRARY_VALUE)?,
+        )),
+        ..Default::default()
+    };
+    let n_storage_keys = n_items(u)?;
+    let mut accessed_storage_keys = HashSet::new();
+    let mut read_storage_keys = HashSet::new();
+    let mut written_storage_keys = HashSet::new();
+    for _ in 0..n_storage_keys {
+        let storage_key = StorageKey::from(u128::from(u.int_in_range(0..=MAX_ARBITRARY_VALUE)?));
+        let (is_read, is_written) = *u.choose(&[(true, false), (false, true), (true, true)])?;
+        accessed_storage_keys.insert(storage_key);
+        if is_read {
+            read_storage_keys.insert(storage_key);
+        }
+        if is_written {
+            written_storage_keys.insert(storage_key);
+        }
+    }
+    let execution = CallExecution::arbitrary(u)?;
+
+    let n_inner_calls =
+        if depth_budget == 0 { 0 } else { u.int_in_range(0..=MAX_ARBITRARY_INNER_CALLS)? };
+    let inner_calls = (0..n_inner_calls)
+        .map(|_| arbitrary_call_info(u, depth_budget - 1))
+        .collect::<Result<_>>()?;
+
+    Ok(CallInfo {
+        call,
+        execution,
+        inner_calls,
+        accessed_storage_keys,
+        read_storage_keys,
+        written_storage_keys,
+        ..Default::default()
+    })
+}
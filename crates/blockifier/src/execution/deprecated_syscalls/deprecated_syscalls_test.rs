@@ -160,6 +160,8 @@ fn test_nested_library_call() {
         resources: storage_entry_point_resources.clone(),
         storage_read_values: vec![felt!(value + 1)],
         accessed_storage_keys: HashSet::from([storage_key!(key + 1)]),
+        read_storage_keys: HashSet::from([storage_key!(key + 1)]),
+        written_storage_keys: HashSet::from([storage_key!(key + 1)]),
         ..Default::default()
     };
     let mut library_call_resources = &get_syscall_resources(DeprecatedSyscallSelector::LibraryCall)
@@ -182,6 +184,8 @@ fn test_nested_library_call() {
         resources: storage_entry_point_resources.clone(),
         storage_read_values: vec![felt!(value)],
         accessed_storage_keys: HashSet::from([storage_key!(key)]),
+        read_storage_keys: HashSet::from([storage_key!(key)]),
+        written_storage_keys: HashSet::from([storage_key!(key)]),
         ..Default::default()
     };
 
@@ -248,6 +252,8 @@ fn test_call_contract() {
         },
         storage_read_values: vec![value],
         accessed_storage_keys: HashSet::from([storage_key!(key_int)]),
+        read_storage_keys: HashSet::from([storage_key!(key_int)]),
+        written_storage_keys: HashSet::from([storage_key!(key_int)]),
         ..Default::default()
     };
     let expected_call_info = CallInfo {
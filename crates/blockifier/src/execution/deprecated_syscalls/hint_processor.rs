@@ -183,6 +183,8 @@ pub struct DeprecatedSyscallHintProcessor<'a> {
     // Additional information gathered during execution.
     pub read_values: Vec<Felt>,
     pub accessed_keys: HashSet<StorageKey>,
+    pub read_keys: HashSet<StorageKey>,
+    pub written_keys: HashSet<StorageKey>,
 
     // Additional fields.
     // Invariant: must only contain allowed hints.
@@ -215,6 +217,8 @@ impl<'a> DeprecatedSyscallHintProcessor<'a> {
             syscall_ptr: initial_syscall_ptr,
             read_values: vec![],
             accessed_keys: HashSet::new(),
+            read_keys: HashSet::new(),
+            written_keys: HashSet::new(),
             builtin_hint_processor: extended_builtin_hint_processor(),
             tx_signature_start_ptr: None,
             tx_info_start_ptr: None,
@@ -408,6 +412,7 @@ impl<'a> DeprecatedSyscallHintProcessor<'a> {
         key: StorageKey,
     ) -> DeprecatedSyscallResult<StorageReadResponse> {
         self.accessed_keys.insert(key);
+        self.read_keys.insert(key);
         let value = self.state.get_storage_at(self.storage_address, key)?;
         self.read_values.push(value);
 
@@ -420,6 +425,7 @@ impl<'a> DeprecatedSyscallHintProcessor<'a> {
         value: Felt,
     ) -> DeprecatedSyscallResult<StorageWriteResponse> {
         self.accessed_keys.insert(key);
+        self.written_keys.insert(key);
         self.state.set_storage_at(self.storage_address, key, value)?;
 
         Ok(StorageWriteResponse {})
@@ -429,6 +429,8 @@ pub fn finalize_execution(
         tracked_resource,
         storage_read_values: syscall_handler.read_values,
         accessed_storage_keys: syscall_handler.accessed_keys,
+        read_storage_keys: syscall_handler.read_keys,
+        written_storage_keys: syscall_handler.written_keys,
     })
 }
 
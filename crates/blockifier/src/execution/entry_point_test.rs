@@ -484,7 +484,8 @@ fn test_post_run_validation_security_failure() {
     );
 }
 
-// Tests correct update of the fields: `storage_read_values` and `accessed_storage_keys`.
+// Tests correct update of the fields: `storage_read_values`, `accessed_storage_keys`,
+// `read_storage_keys` and `written_storage_keys`.
 #[test]
 fn test_storage_related_members() {
     let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
@@ -515,6 +516,8 @@ fn test_storage_related_members() {
     let actual_call_info = entry_point_call.execute_directly(&mut state).unwrap();
     assert_eq!(actual_call_info.storage_read_values, vec![value]);
     assert_eq!(actual_call_info.accessed_storage_keys, HashSet::from([storage_key!(key_int)]));
+    assert_eq!(actual_call_info.read_storage_keys, HashSet::from([storage_key!(key_int)]));
+    assert_eq!(actual_call_info.written_storage_keys, HashSet::from([storage_key!(key_int)]));
 }
 
 #[test]
@@ -11,6 +11,7 @@ use num_bigint::{BigInt, TryFromBigIntError};
 use starknet_api::contract_class::EntryPointType;
 use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector};
 use starknet_api::execution_utils::format_panic_data;
+use starknet_api::StarknetApiError;
 use starknet_types_core::felt::Felt;
 use thiserror::Error;
 
@@ -140,3 +141,64 @@ pub enum ContractClassError {
         sierra_program_length: usize,
     },
 }
+
+#[derive(Debug, Error)]
+pub enum SummaryConversionError {
+    #[error("Counter {0} does not fit in usize.")]
+    CounterOutOfRange(u64),
+    #[error(
+        "Inconsistent event order range: min {min_event_order:?}, max {max_event_order:?}; both \
+         or neither must be set, and min must not exceed max."
+    )]
+    InconsistentEventOrderRange { min_event_order: Option<u64>, max_event_order: Option<u64> },
+    #[error("Parallel fields {fields} have mismatched lengths.")]
+    MismatchedLengths { fields: &'static str },
+    #[error(transparent)]
+    StarknetApiError(#[from] StarknetApiError),
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum SummaryConsistencyError {
+    #[error("Expected {n_messages} messages, but the summary has {n_payload_lengths} payloads.")]
+    MessageCountMismatch { n_messages: usize, n_payload_lengths: usize },
+    #[error("The summary has {empty_event_count} empty events out of only {n_events} events.")]
+    TooManyEmptyEvents { empty_event_count: usize, n_events: usize },
+    #[error(
+        "The largest event has {max_single_event_data} data felts, more than all events together \
+         ({total_event_data_size})."
+    )]
+    EventDataSizeMismatch { max_single_event_data: usize, total_event_data_size: u64 },
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum SummaryError {
+    #[error("The call tree has no call at path {path:?}.")]
+    InvalidCallPath { path: Vec<usize> },
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum PayloadBudgetError {
+    #[error(
+        "Message payloads take at least {accumulated_bytes} bytes, exceeding the budget of \
+         {max_total_bytes} bytes by {} bytes.",
+        .accumulated_bytes - .max_total_bytes
+    )]
+    BudgetExceeded { accumulated_bytes: usize, max_total_bytes: usize },
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum WalkLimitError {
+    #[error("The call trees have more than {max_nodes} calls.")]
+    LimitExceeded { max_nodes: usize },
+}
+
+#[cfg(feature = "transaction_serde")]
+#[derive(Debug, Error)]
+pub enum DecodeError {
+    #[error("The encoding is empty; expected at least a version byte.")]
+    MissingVersion,
+    #[error("Unsupported encoding version {version}.")]
+    UnsupportedVersion { version: u8 },
+    #[error(transparent)]
+    Bincode(#[from] bincode::Error),
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use crate::execution::call_info::{CallInfo, ExecutionSummary, SummaryAccumulator};
+use crate::execution::errors::SummaryError;
+
+#[cfg(test)]
+#[path = "summary_index_test.rs"]
+pub mod summary_index_test;
+
+/// Identifies a call in a call tree by the indices of the inner calls leading to it from the root;
+/// the root's path is empty.
+pub type CallPath = Vec<usize>;
+
+/// Memoizes the summaries of all subtrees of a call tree, so that after a call is mutated, only the
+/// summaries of its subtree and of its ancestors are recomputed (see [`Self::update`]).
+#[derive(Debug, Default)]
+pub struct SummaryIndex {
+    subtree_summaries: HashMap<CallPath, ExecutionSummary>,
+}
+
+impl SummaryIndex {
+    pub fn new(root: &CallInfo) -> Self {
+        let mut index = Self::default();
+        index.index_subtree(root, &mut CallPath::new(), &mut Vec::new());
+        index
+    }
+
+    /// Returns the summary of the whole tree; equals the root's [`CallInfo::summarize`].
+    pub fn summary(&self) -> &ExecutionSummary {
+        &self.subtree_summaries[&CallPath::new()]
+    }
+
+    pub fn subtree_summary(&self, path: &[usize]) -> Option<&ExecutionSummary> {
+        self.subtree_summaries.get(path)
+    }
+
+    /// Recomputes the cached summaries after the call at `path` in `root` (possibly including its
+    /// inner calls) was mutated; the rest of the tree must be unchanged since the last indexing.
+    /// Returns the paths of the recomputed subtrees, in recomputation order (the root last).
+    pub fn update(
+        &mut self,
+        root: &CallInfo,
+        path: &[usize],
+    ) -> Result<Vec<CallPath>, SummaryError> {
+        let mut ancestors = vec![root];
+        for &index in path {
+            let parent = ancestors[ancestors.len() - 1];
+            let Some(call_info) = parent.inner_calls.get(index) else {
+                return Err(SummaryError::InvalidCallPath { path: path.to_vec() });
+            };
+            ancestors.push(call_info);
+        }
+
+        // The mutated call may have gained or lost inner calls; drop its stale descendants.
+        self.subtree_summaries.retain(|cached_path, _| !cached_path.starts_with(path));
+        let mut recomputed_paths = Vec::new();
+        let mut current_path = path.to_vec();
+        let changed_call = ancestors.pop().expect("The root is always an ancestor.");
+        self.index_subtree(changed_call, &mut current_path, &mut recomputed_paths);
+
+        while let Some(call_info) = ancestors.pop() {
+            current_path.pop();
+            self.recompute(call_info, &current_path);
+            recomputed_paths.push(current_path.clone());
+        }
+        Ok(recomputed_paths)
+    }
+
+    // Summarizes the subtree at `path` bottom-up, caching the summary of each of its subtrees.
+    fn index_subtree(
+        &mut self,
+        call_info: &CallInfo,
+        path: &mut CallPath,
+        recomputed_paths: &mut Vec<CallPath>,
+    ) {
+        for (index, inner_call) in call_info.inner_calls.iter().enumerate() {
+            path.push(index);
+            self.index_subtree(inner_call, path, recomputed_paths);
+            path.pop();
+        }
+        self.recompute(call_info, path);
+        recomputed_paths.push(path.clone());
+    }
+
+    // Recomputes the summary of the subtree at `path` from the cached summaries of its inner calls.
+    fn recompute(&mut self, call_info: &CallInfo, path: &[usize]) {
+        let mut summary = ExecutionSummary::default();
+        summary.visit_call(call_info, path.len());
+        let mut inner_call_path = path.to_vec();
+        for index in 0..call_info.inner_calls.len() {
+            inner_call_path.push(index);
+            summary += &self.subtree_summaries[&inner_call_path];
+            inner_call_path.pop();
+        }
+        self.subtree_summaries.insert(path.to_vec(), summary);
+    }
+}
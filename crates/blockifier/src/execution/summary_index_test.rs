@@ -0,0 +1,69 @@
+use starknet_api::class_hash;
+use starknet_api::transaction::EventContent;
+
+use crate::execution::call_info::{CallExecution, CallInfo, OrderedEvent};
+use crate::execution::entry_point::CallEntryPoint;
+use crate::execution::errors::SummaryError;
+use crate::execution::summary_index::SummaryIndex;
+
+// Summarizing requires every call to have a class hash.
+fn call_info_with_inner_calls(inner_calls: Vec<CallInfo>) -> CallInfo {
+    CallInfo {
+        call: CallEntryPoint { class_hash: Some(class_hash!("0x1")), ..Default::default() },
+        inner_calls,
+        ..Default::default()
+    }
+}
+
+// A root with two inner calls, the first of which has two inner calls of its own.
+fn call_tree() -> CallInfo {
+    call_info_with_inner_calls(vec![
+        call_info_with_inner_calls(vec![
+            call_info_with_inner_calls(vec![]),
+            call_info_with_inner_calls(vec![]),
+        ]),
+        call_info_with_inner_calls(vec![]),
+    ])
+}
+
+#[test]
+fn test_update_recomputes_only_ancestors() {
+    let mut root = call_tree();
+    let mut index = SummaryIndex::new(&root);
+    assert_eq!(index.summary(), &root.summarize());
+    let untouched_summary = index.subtree_summary(&[1]).unwrap().clone();
+
+    root.inner_calls[0].inner_calls[1].execution = CallExecution {
+        events: vec![OrderedEvent { order: 0, event: EventContent::default() }],
+        ..Default::default()
+    };
+    let recomputed_paths = index.update(&root, &[0, 1]).unwrap();
+
+    assert_eq!(recomputed_paths, vec![vec![0, 1], vec![0], vec![]]);
+    assert_eq!(index.summary(), &root.summarize());
+    assert_eq!(index.subtree_summary(&[1]), Some(&untouched_summary));
+    assert_eq!(index.summary().event_summary.n_events, 1);
+}
+
+#[test]
+fn test_update_reindexes_changed_inner_calls() {
+    let mut root = call_tree();
+    let mut index = SummaryIndex::new(&root);
+
+    root.inner_calls[0].inner_calls.pop();
+    let recomputed_paths = index.update(&root, &[0]).unwrap();
+
+    assert_eq!(recomputed_paths, vec![vec![0, 0], vec![0], vec![]]);
+    assert_eq!(index.subtree_summary(&[0, 1]), None);
+    assert_eq!(index.summary(), &root.summarize());
+}
+
+#[test]
+fn test_update_invalid_path() {
+    let root = call_tree();
+    let mut index = SummaryIndex::new(&root);
+    assert_eq!(
+        index.update(&root, &[1, 0]),
+        Err(SummaryError::InvalidCallPath { path: vec![1, 0] })
+    );
+}
@@ -225,6 +225,8 @@ pub struct SyscallHintProcessor<'a> {
     // Additional information gathered during execution.
     pub read_values: Vec<Felt>,
     pub accessed_keys: HashSet<StorageKey>,
+    pub read_keys: HashSet<StorageKey>,
+    pub written_keys: HashSet<StorageKey>,
 
     // The original storage value of the executed contract.
     // Should be moved back `context.revert_info` before executing an inner call.
@@ -273,6 +275,8 @@ impl<'a> SyscallHintProcessor<'a> {
             syscall_ptr: initial_syscall_ptr,
             read_values: vec![],
             accessed_keys: HashSet::new(),
+            read_keys: HashSet::new(),
+            written_keys: HashSet::new(),
             original_values,
             hints,
             execution_info_ptr: None,
@@ -679,6 +683,7 @@ impl<'a> SyscallHintProcessor<'a> {
         key: StorageKey,
     ) -> SyscallResult<StorageReadResponse> {
         self.accessed_keys.insert(key);
+        self.read_keys.insert(key);
         let value = self.state.get_storage_at(self.storage_address(), key)?;
         self.read_values.push(value);
 
@@ -700,6 +705,7 @@ impl<'a> SyscallHintProcessor<'a> {
         }
 
         self.accessed_keys.insert(key);
+        self.written_keys.insert(key);
         self.state.set_storage_at(contract_address, key, value)?;
 
         Ok(StorageWriteResponse {})
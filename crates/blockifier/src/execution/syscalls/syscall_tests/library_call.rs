@@ -165,6 +165,8 @@ fn test_nested_library_call(test_contract: FeatureContract, expected_gas: u64) {
         tracked_resource,
         storage_read_values: vec![felt!(value + 1)],
         accessed_storage_keys: HashSet::from([StorageKey(patricia_key!(key + 1))]),
+        read_storage_keys: HashSet::from([StorageKey(patricia_key!(key + 1))]),
+        written_storage_keys: HashSet::from([StorageKey(patricia_key!(key + 1))]),
         ..Default::default()
     };
 
@@ -198,6 +200,8 @@ fn test_nested_library_call(test_contract: FeatureContract, expected_gas: u64) {
         resources: storage_entry_point_resources,
         storage_read_values: vec![felt!(value)],
         accessed_storage_keys: HashSet::from([StorageKey(patricia_key!(key))]),
+        read_storage_keys: HashSet::from([StorageKey(patricia_key!(key))]),
+        written_storage_keys: HashSet::from([StorageKey(patricia_key!(key))]),
         tracked_resource,
         ..Default::default()
     };
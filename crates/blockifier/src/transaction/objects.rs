@@ -1,14 +1,19 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
-use starknet_api::core::{ContractAddress, Nonce};
+use itertools::Itertools;
+use starknet_api::block_hash::event_commitment::{calculate_event_commitment, EventLeafElement};
+use starknet_api::core::{ClassHash, ContractAddress, EthAddress, Nonce};
 use starknet_api::data_availability::DataAvailabilityMode;
 use starknet_api::execution_resources::GasVector;
+use starknet_api::state::StorageKey;
 use starknet_api::transaction::{
     signed_tx_version,
     AccountDeploymentData,
     AllResourceBounds,
+    Event,
+    EventContent,
     Fee,
     PaymasterData,
     ResourceBounds,
@@ -19,13 +24,26 @@ use starknet_api::transaction::{
     TransactionVersion,
     ValidResourceBounds,
 };
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::StarkHash;
 use strum_macros::EnumIter;
 
 use crate::abi::constants as abi_constants;
 use crate::blockifier::block::BlockInfo;
-use crate::execution::call_info::{CallInfo, ExecutionSummary};
+use crate::execution::call_info::{
+    CallInfo,
+    ExecutionSummary,
+    FeeBreakdown,
+    MessageToL1,
+    SummaryAccumulator,
+    SummaryWeights,
+    FELT_SIZE_IN_BYTES,
+};
+use crate::execution::entry_point::CallEntryPoint;
+use crate::execution::errors::{PayloadBudgetError, WalkLimitError};
 use crate::fee::fee_utils::get_fee_by_gas_vector;
 use crate::fee::receipt::TransactionReceipt;
+use crate::state::cached_state::StorageEntry;
 use crate::transaction::errors::{TransactionExecutionError, TransactionPreValidationError};
 
 #[cfg(test)]
@@ -157,6 +175,74 @@ pub struct TransactionExecutionInfo {
     pub receipt: TransactionReceipt,
 }
 
+/// The class hash reported in call frames for calls without a class hash.
+pub const UNKNOWN_CLASS_HASH_FRAME: ClassHash = ClassHash(Felt::ZERO);
+
+/// The phase of a transaction's execution.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Phase {
+    Validate,
+    Execute,
+    FeeTransfer,
+}
+
+/// An event emitted by a transaction, as reported in its receipt.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ReceiptEvent<'a> {
+    pub phase: Phase,
+    pub from_address: ContractAddress,
+    pub content: &'a EventContent,
+}
+
+/// An L2-to-L1 message sent by a transaction, as reported in its receipt.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptMessage {
+    pub from_address: ContractAddress,
+    pub to_address: EthAddress,
+    pub payload: Vec<Felt>,
+}
+
+/// Scalar counters over a transaction's call trees, computed without building a full summary;
+/// see [TransactionExecutionInfo::quick_stats].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct QuickStats {
+    pub n_events: usize,
+    pub n_messages: usize,
+    pub n_calls: usize,
+    // Not deduplicated; a key accessed by several calls is counted once per call.
+    pub n_storage_accesses: usize,
+    // The depth of the deepest call (root calls are at depth 0); 0 if there are no calls.
+    pub max_depth: usize,
+}
+
+impl QuickStats {
+    fn visit_call(&mut self, call_info: &CallInfo, depth: usize) {
+        self.n_events += call_info.execution.events.len();
+        self.n_messages += call_info.execution.l2_to_l1_messages.len();
+        self.n_calls += 1;
+        self.n_storage_accesses += call_info.accessed_storage_keys.len();
+        self.max_depth = self.max_depth.max(depth);
+    }
+
+    // Recurses rather than keeping a stack of pending calls, so that nothing is allocated; the
+    // recursion is as deep as the call tree, which the execution bounds.
+    fn visit_call_tree(&mut self, call_info: &CallInfo, depth: usize) {
+        self.visit_call(call_info, depth);
+        for inner_call in &call_info.inner_calls {
+            self.visit_call_tree(inner_call, depth + 1);
+        }
+    }
+}
+
+/// A per-call issue encountered by [TransactionExecutionInfo::summarize_lossy].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SummaryWarning {
+    /// The call has no class hash; its other effects were still summarized.
+    MissingClassHash { storage_address: ContractAddress },
+    /// The call's events are not sorted by order; they were still summarized.
+    OutOfOrderEvents { storage_address: ContractAddress },
+}
+
 impl TransactionExecutionInfo {
     pub fn non_optional_call_infos(&self) -> impl Iterator<Item = &CallInfo> {
         self.validate_call_info
@@ -174,7 +260,572 @@ impl TransactionExecutionInfo {
     pub fn summarize(&self) -> ExecutionSummary {
         CallInfo::summarize_many(self.non_optional_call_infos())
     }
+
+    /// Like [`Self::summarize`], but only computes scalar counters, in a single pass over the call
+    /// trees that allocates nothing; meant for hot paths such as mempool admission.
+    pub fn quick_stats(&self) -> QuickStats {
+        let mut stats = QuickStats::default();
+        for call_info in self.non_optional_call_infos() {
+            stats.visit_call_tree(call_info, 0);
+        }
+        stats
+    }
+
+    /// Returns the distinct class hashes executed by the transaction, in the order they were first
+    /// executed (i.e., first visited). Calls without a class hash are skipped.
+    pub fn class_hashes_in_order(&self) -> Vec<ClassHash> {
+        self.non_optional_call_infos()
+            .flat_map(CallInfo::iter)
+            .filter_map(|call_info| call_info.call.class_hash)
+            .unique()
+            .collect()
+    }
+
+    /// Combines the execute call trees of the given transactions under a synthetic root call, so
+    /// that the call tree helpers (e.g., [CallInfo::summarize]) apply to all of them at once.
+    /// The root has no effects of its own and is attributed to [UNKNOWN_CLASS_HASH_FRAME].
+    pub fn merge_execute_trees(infos: &[TransactionExecutionInfo]) -> CallInfo {
+        CallInfo {
+            call: CallEntryPoint {
+                class_hash: Some(UNKNOWN_CLASS_HASH_FRAME),
+                ..Default::default()
+            },
+            inner_calls: infos.iter().filter_map(|info| info.execute_call_info.clone()).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the storage keys accessed by the transaction, grouped by contract; both the
+    /// contracts and each contract's keys are sorted, making the list deterministic.
+    pub fn access_list(&self) -> Vec<(ContractAddress, Vec<StorageKey>)> {
+        let mut keys_by_contract: BTreeMap<ContractAddress, BTreeSet<StorageKey>> = BTreeMap::new();
+        self.walk_calls(|call_info, _| {
+            keys_by_contract
+                .entry(call_info.call.storage_address)
+                .or_default()
+                .extend(call_info.accessed_storage_keys.iter().copied());
+        });
+
+        keys_by_contract
+            .into_iter()
+            .filter(|(_, storage_keys)| !storage_keys.is_empty())
+            .map(|(contract_address, storage_keys)| {
+                (contract_address, storage_keys.into_iter().collect())
+            })
+            .collect()
+    }
+
+    /// Returns the storage entries that the transaction both read and wrote, possibly in different
+    /// calls, e.g., for detecting read-after-write conflicts.
+    pub fn read_modify_write_entries(&self) -> HashSet<StorageEntry> {
+        let mut read_entries = HashSet::new();
+        let mut written_entries = HashSet::new();
+        self.walk_calls(|call_info, _| {
+            let storage_address = call_info.call.storage_address;
+            read_entries.extend(
+                call_info
+                    .read_storage_keys
+                    .iter()
+                    .map(|storage_key| (storage_address, *storage_key)),
+            );
+            written_entries.extend(
+                call_info
+                    .written_storage_keys
+                    .iter()
+                    .map(|storage_key| (storage_address, *storage_key)),
+            );
+        });
+
+        read_entries.intersection(&written_entries).copied().collect()
+    }
+
+    /// Returns the summary of the validation phase alone, if it ran; this is available also for
+    /// transactions that failed validation and thus have no execution phase.
+    pub fn validation_summary(&self) -> Option<ExecutionSummary> {
+        self.validate_call_info.as_ref().map(CallInfo::summarize)
+    }
+
+    /// Like [Self::summarize], but leaves out the payload lengths of L2-to-L1 messages with an
+    /// empty payload.
+    pub fn summarize_nonempty_messages(&self) -> ExecutionSummary {
+        let mut summary = self.summarize();
+        summary.l2_to_l1_payload_lengths.retain(|&payload_length| payload_length > 0);
+        summary
+    }
+
+    /// Like [Self::summarize], but writes the summary into `out` (which is cleared first) to reuse
+    /// its allocations.
+    pub fn summarize_into(&self, out: &mut ExecutionSummary) {
+        out.clear();
+        self.accumulate(out);
+    }
+
+    /// Drives the given accumulator over all calls of the transaction, in pre-order.
+    pub fn accumulate<A: SummaryAccumulator>(&self, accumulator: &mut A) {
+        self.walk_calls(|call_info, depth| accumulator.visit_call(call_info, depth));
+    }
+
+    /// Like [Self::summarize], but never panics on a malformed call: only the class hash of a call
+    /// without one is missing from the summary, and every issue is reported as a warning alongside
+    /// the summary.
+    pub fn summarize_lossy(&self) -> (ExecutionSummary, Vec<SummaryWarning>) {
+        let mut summary = ExecutionSummary::default();
+        let mut warnings = Vec::new();
+        for call_info in self.non_optional_call_infos().flat_map(CallInfo::iter) {
+            let storage_address = call_info.call.storage_address;
+            let events = &call_info.execution.events;
+            if !events.windows(2).all(|pair| pair[0].order <= pair[1].order) {
+                warnings.push(SummaryWarning::OutOfOrderEvents { storage_address });
+            }
+            if call_info.call.class_hash.is_none() {
+                warnings.push(SummaryWarning::MissingClassHash { storage_address });
+            }
+            summary.visit_call_lossy(call_info);
+        }
+
+        (summary, warnings)
+    }
+
+    /// Like [Self::summarize], but only includes the own effects of calls passing the given
+    /// predicate (e.g., an allowlist of contracts). The inner calls of a rejected call are still
+    /// visited, and are included if they pass the predicate themselves.
+    pub fn summarize_filtered(&self, predicate: impl Fn(&CallInfo) -> bool) -> ExecutionSummary {
+        CallInfo::summarize_calls(
+            self.non_optional_call_infos()
+                .flat_map(CallInfo::iter_with_depth)
+                .filter(|(call_info, _)| predicate(call_info)),
+        )
+    }
+
+    /// Like [Self::summarize], but with the on-chain accounting of reverted calls: a failed call
+    /// and all of its inner calls still count their storage reads, but none of their storage
+    /// writes, events or messages.
+    pub fn summarize_with_revert_semantics(&self) -> ExecutionSummary {
+        let mut summary = ExecutionSummary::default();
+        let mut reverted_depth = None;
+        for (call_info, depth) in self.non_optional_call_infos().flat_map(CallInfo::iter_with_depth)
+        {
+            // Calls are visited in pre-order, so leaving a reverted subtree means returning to the
+            // depth of its root, or above it.
+            if reverted_depth.is_some_and(|reverted_depth| depth <= reverted_depth) {
+                reverted_depth = None;
+            }
+            if reverted_depth.is_none() && call_info.execution.failed {
+                reverted_depth = Some(depth);
+            }
+            summary.visit_call_with_revert(call_info, reverted_depth.is_some());
+        }
+
+        summary
+    }
+
+    /// Returns whether any call in the transaction wrote to storage, emitted an event or sent an
+    /// L2-to-L1 message.
+    pub fn has_external_effects(&self) -> bool {
+        self.non_optional_call_infos().flat_map(CallInfo::iter).any(|call_info| {
+            !call_info.written_storage_keys.is_empty()
+                || !call_info.execution.events.is_empty()
+                || !call_info.execution.l2_to_l1_messages.is_empty()
+        })
+    }
+
+    /// Like [Self::summarize], but fails as soon as the accumulated size of the L2-to-L1 message
+    /// payloads (in bytes, each felt taking [FELT_SIZE_IN_BYTES]) exceeds `max_total_bytes`.
+    pub fn summarize_with_payload_budget(
+        &self,
+        max_total_bytes: usize,
+    ) -> Result<ExecutionSummary, PayloadBudgetError> {
+        let mut accumulated_bytes = 0;
+        self.try_walk_calls(|call_info, _| {
+            for message in &call_info.execution.l2_to_l1_messages {
+                accumulated_bytes += message.message.payload.0.len() * FELT_SIZE_IN_BYTES;
+                if accumulated_bytes > max_total_bytes {
+                    return Err(PayloadBudgetError::BudgetExceeded {
+                        accumulated_bytes,
+                        max_total_bytes,
+                    });
+                }
+            }
+            Ok(())
+        })?;
+
+        Ok(self.summarize())
+    }
+
+    /// Returns a preview of the transaction's weighted cost, split by cost driver.
+    pub fn fee_preview(&self, weights: &SummaryWeights) -> FeeBreakdown {
+        self.summarize().fee_breakdown(weights)
+    }
+
+    /// Visits every call in the transaction's call trees in pre-order, along with its depth (root
+    /// calls are at depth 0).
+    pub fn walk_calls<F: FnMut(&CallInfo, usize)>(&self, mut f: F) {
+        self.non_optional_call_infos()
+            .flat_map(CallInfo::iter_with_depth)
+            .for_each(|(call_info, depth)| f(call_info, depth));
+    }
+
+    /// Returns every call in the transaction's call trees in pre-order (the order of
+    /// [`Self::walk_calls`]), paired with its index in that order.
+    pub fn enumerate_calls(&self) -> Vec<(usize, &CallInfo)> {
+        self.non_optional_call_infos().flat_map(CallInfo::iter).enumerate().collect()
+    }
+
+    /// Like [`Self::walk_calls`], but halts the traversal on the first error returned by the
+    /// visitor.
+    pub fn try_walk_calls<E, F: FnMut(&CallInfo, usize) -> Result<(), E>>(
+        &self,
+        mut f: F,
+    ) -> Result<(), E> {
+        self.non_optional_call_infos()
+            .flat_map(CallInfo::iter_with_depth)
+            .try_for_each(|(call_info, depth)| f(call_info, depth))
+    }
+
+    /// Like [`Self::walk_calls`], but visits at most `max_nodes` calls, e.g., to bound the work
+    /// spent on untrusted input; errors if the call trees have more calls than that.
+    pub fn walk_calls_bounded<F: FnMut(&CallInfo, usize)>(
+        &self,
+        max_nodes: usize,
+        mut f: F,
+    ) -> Result<(), WalkLimitError> {
+        let mut n_visited_calls = 0;
+        self.try_walk_calls(|call_info, depth| {
+            if n_visited_calls == max_nodes {
+                return Err(WalkLimitError::LimitExceeded { max_nodes });
+            }
+            n_visited_calls += 1;
+            f(call_info, depth);
+            Ok(())
+        })
+    }
+
+    /// Returns the non-optional call infos, each tagged with the phase it was executed in.
+    fn call_infos_by_phase(&self) -> impl Iterator<Item = (Phase, &CallInfo)> {
+        let validate = self.validate_call_info.iter().map(|call_info| (Phase::Validate, call_info));
+        let execute = self.execute_call_info.iter().map(|call_info| (Phase::Execute, call_info));
+        let fee_transfer =
+            self.fee_transfer_call_info.iter().map(|call_info| (Phase::FeeTransfer, call_info));
+        validate.chain(execute).chain(fee_transfer)
+    }
+
+    /// Returns all events emitted by the transaction in receipt order; i.e., by phase (validate,
+    /// execute, fee transfer), and within each phase by event order.
+    pub fn receipt_events(&self) -> Vec<ReceiptEvent<'_>> {
+        self.call_infos_by_phase()
+            .flat_map(|(phase, call_info)| {
+                call_info
+                    .iter()
+                    .flat_map(|inner_call| {
+                        let from_address = inner_call.call.storage_address;
+                        inner_call
+                            .execution
+                            .events
+                            .iter()
+                            .map(move |ordered_event| (from_address, ordered_event))
+                    })
+                    .sorted_by_key(|(_, ordered_event)| ordered_event.order)
+                    .map(move |(from_address, ordered_event)| ReceiptEvent {
+                        phase,
+                        from_address,
+                        content: &ordered_event.event,
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns all L2-to-L1 messages sent by the transaction in receipt order; i.e., by phase
+    /// (validate, execute, fee transfer), and within each phase by message order. Ties (only in
+    /// malformed call infos) are broken by the pre-order traversal of the calls.
+    pub fn receipt_messages(&self) -> Vec<ReceiptMessage> {
+        self.call_infos_by_phase()
+            .flat_map(|(_, call_info)| {
+                call_info
+                    .iter()
+                    .flat_map(|inner_call| {
+                        let from_address = inner_call.call.storage_address;
+                        inner_call
+                            .execution
+                            .l2_to_l1_messages
+                            .iter()
+                            .map(move |ordered_message| (from_address, ordered_message))
+                    })
+                    .sorted_by_key(|(_, ordered_message)| ordered_message.order)
+                    .map(|(from_address, ordered_message)| ReceiptMessage {
+                        from_address,
+                        to_address: ordered_message.message.to_address,
+                        payload: ordered_message.message.payload.0.clone(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the events commitment of the transaction; i.e., the root of the Patricia tree whose
+    /// leaves are the hashes of its receipt events, all hashed with H. With Poseidon, this is the
+    /// commitment used in the block hash; [Keccak](starknet_api::hash::Keccak) suits commitments
+    /// verified on L1.
+    /// The transaction hash is not part of the execution info, but each event leaf commits to the
+    /// hash of its emitting transaction (as in the block's event commitment), so the caller must
+    /// provide it.
+    pub fn events_commitment<H: StarkHash>(&self, transaction_hash: TransactionHash) -> Felt {
+        let event_leaf_elements: Vec<EventLeafElement> = self
+            .receipt_events()
+            .into_iter()
+            .map(|receipt_event| {
+                let event = Event {
+                    from_address: receipt_event.from_address,
+                    content: receipt_event.content.clone(),
+                };
+                EventLeafElement::new(event, transaction_hash)
+            })
+            .collect();
+        calculate_event_commitment::<H>(&event_leaf_elements).0
+    }
+
+    /// Returns the keys of all events emitted by the transaction, flattened in receipt order.
+    pub fn all_event_key_felts(&self) -> Vec<Felt> {
+        self.receipt_events()
+            .into_iter()
+            .flat_map(|receipt_event| receipt_event.content.keys.iter().map(|key| key.0))
+            .collect()
+    }
+
+    /// Returns the largest number of direct inner calls of any call in the transaction, along with
+    /// the first such call in pre-order (or `None` if the transaction has no calls).
+    pub fn widest_call(&self) -> (usize, Option<&CallInfo>) {
+        self.non_optional_call_infos().flat_map(CallInfo::iter).fold(
+            (0, None),
+            |(max_width, widest_call), call_info| {
+                let width = call_info.inner_calls.len();
+                if widest_call.is_none() || width > max_width {
+                    (width, Some(call_info))
+                } else {
+                    (max_width, widest_call)
+                }
+            },
+        )
+    }
+
+    /// Returns the `(depth, class_hash)` frames of all calls in pre-order, e.g., for generating
+    /// folded stacks. Calls without a class hash are reported with [UNKNOWN_CLASS_HASH_FRAME].
+    pub fn call_frames(&self) -> Vec<(usize, ClassHash)> {
+        self.non_optional_call_infos()
+            .flat_map(CallInfo::iter_with_depth)
+            .map(|(call_info, depth)| {
+                (depth, call_info.call.class_hash.unwrap_or(UNKNOWN_CLASS_HASH_FRAME))
+            })
+            .collect()
+    }
+
+    /// Returns the class hashes along the longest root-to-leaf path of any call tree of the
+    /// transaction, from the root down; of equally long paths, the first in pre-order is returned.
+    /// Calls without a class hash are reported with [UNKNOWN_CLASS_HASH_FRAME].
+    pub fn deepest_path(&self) -> Vec<ClassHash> {
+        let mut path = Vec::new();
+        let mut deepest_path = Vec::new();
+        for (call_info, depth) in self.non_optional_call_infos().flat_map(CallInfo::iter_with_depth)
+        {
+            // Calls are visited in pre-order, so the path holds the ancestors of the current call.
+            path.truncate(depth);
+            path.push(call_info.call.class_hash.unwrap_or(UNKNOWN_CLASS_HASH_FRAME));
+            if path.len() > deepest_path.len() {
+                deepest_path.clone_from(&path);
+            }
+        }
+        deepest_path
+    }
+
+    /// Returns the calls that emitted events but have no class hash; such calls are most likely
+    /// the result of a malformed call info.
+    pub fn suspicious_calls(&self) -> Vec<&CallInfo> {
+        self.non_optional_call_infos()
+            .flat_map(CallInfo::iter)
+            .filter(|call_info| {
+                !call_info.execution.events.is_empty() && call_info.call.class_hash.is_none()
+            })
+            .collect()
+    }
+
+    /// Returns the calls that emitted more than `per_call_limit` events themselves, in pre-order;
+    /// events emitted by their inner calls are not counted.
+    pub fn calls_exceeding_event_limit(&self, per_call_limit: usize) -> Vec<&CallInfo> {
+        self.non_optional_call_infos()
+            .flat_map(CallInfo::iter)
+            .filter(|call_info| call_info.execution.events.len() > per_call_limit)
+            .collect()
+    }
+
+    /// Returns the calls that neither accessed storage, emitted events nor sent messages
+    /// themselves, in pre-order; e.g., pure computations, which may be dead code. The effects of
+    /// their inner calls are not considered.
+    pub fn effectless_calls(&self) -> Vec<&CallInfo> {
+        self.non_optional_call_infos()
+            .flat_map(CallInfo::iter)
+            .filter(|call_info| {
+                call_info.accessed_storage_keys.is_empty()
+                    && call_info.execution.events.is_empty()
+                    && call_info.execution.l2_to_l1_messages.is_empty()
+            })
+            .collect()
+    }
+
+    /// Groups all L2-to-L1 messages sent by the transaction by their L1 destination address.
+    /// Within each group, messages keep their receipt order.
+    pub fn messages_by_destination(&self) -> HashMap<EthAddress, Vec<&MessageToL1>> {
+        let mut messages_by_destination: HashMap<EthAddress, Vec<&MessageToL1>> = HashMap::new();
+        let ordered_messages = self.non_optional_call_infos().flat_map(|call_info| {
+            call_info
+                .iter()
+                .flat_map(|inner_call| inner_call.execution.l2_to_l1_messages.iter())
+                .sorted_by_key(|ordered_message| ordered_message.order)
+        });
+        for ordered_message in ordered_messages {
+            let message = &ordered_message.message;
+            messages_by_destination.entry(message.to_address).or_default().push(message);
+        }
+
+        messages_by_destination
+    }
+
+    /// Returns the number of calls made to each entry point, keyed by class hash and selector.
+    /// Calls without a class hash are skipped.
+    pub fn selector_call_counts(&self) -> HashMap<(ClassHash, Felt), usize> {
+        let mut selector_call_counts = HashMap::new();
+        for call_info in self.non_optional_call_infos().flat_map(CallInfo::iter) {
+            let Some(class_hash) = call_info.call.class_hash else {
+                continue;
+            };
+            *selector_call_counts
+                .entry((class_hash, call_info.call.entry_point_selector.0))
+                .or_default() += 1;
+        }
+        selector_call_counts
+    }
+
+    /// Returns the distinct entry point selectors invoked by the transaction, regardless of class.
+    pub fn distinct_selectors(&self) -> HashSet<Felt> {
+        self.non_optional_call_infos()
+            .flat_map(CallInfo::iter)
+            .map(|call_info| call_info.call.entry_point_selector.0)
+            .collect()
+    }
+
+    /// Returns the number of L2-to-L1 messages sent by each contract that sent any.
+    pub fn messages_by_contract(&self) -> HashMap<ContractAddress, usize> {
+        let mut messages_by_contract = HashMap::new();
+        for call_info in self.non_optional_call_infos().flat_map(CallInfo::iter) {
+            let n_messages = call_info.execution.l2_to_l1_messages.len();
+            if n_messages > 0 {
+                *messages_by_contract.entry(call_info.call.storage_address).or_default() +=
+                    n_messages;
+            }
+        }
+        messages_by_contract
+    }
+
+    /// Returns the number of calls made to each class, regardless of entry point. Calls without a
+    /// class hash are skipped.
+    pub fn class_hash_counts(&self) -> HashMap<ClassHash, usize> {
+        let mut class_hash_counts = HashMap::new();
+        for class_hash in self
+            .non_optional_call_infos()
+            .flat_map(CallInfo::iter)
+            .filter_map(|call_info| call_info.call.class_hash)
+        {
+            *class_hash_counts.entry(class_hash).or_default() += 1;
+        }
+        class_hash_counts
+    }
+
+    /// Returns the number of events emitted by each contract at each depth of its call tree; calls
+    /// without events add no entry.
+    pub fn events_by_depth_and_contract(&self) -> HashMap<(usize, ContractAddress), usize> {
+        let mut events_by_depth_and_contract = HashMap::new();
+        for (call_info, depth) in self.non_optional_call_infos().flat_map(CallInfo::iter_with_depth)
+        {
+            let n_events = call_info.execution.events.len();
+            if n_events > 0 {
+                *events_by_depth_and_contract
+                    .entry((depth, call_info.call.storage_address))
+                    .or_default() += n_events;
+            }
+        }
+        events_by_depth_and_contract
+    }
+
+    /// Returns the number of events emitted at each depth of the call trees, up to the deepest
+    /// call.
+    pub fn events_by_depth(&self) -> Vec<usize> {
+        let mut events_by_depth = Vec::new();
+        self.walk_calls(|call_info, depth| {
+            add_at_depth(&mut events_by_depth, depth, call_info.execution.events.len())
+        });
+        events_by_depth
+    }
+
+    /// Returns the number of storage keys accessed at each depth of the call trees, up to the
+    /// deepest call.
+    pub fn storage_entries_by_depth(&self) -> Vec<usize> {
+        let mut storage_entries_by_depth = Vec::new();
+        self.walk_calls(|call_info, depth| {
+            add_at_depth(&mut storage_entries_by_depth, depth, call_info.accessed_storage_keys.len())
+        });
+        storage_entries_by_depth
+    }
+
+    /// Returns the distinct L1 addresses the transaction sent messages to.
+    pub fn message_destinations(&self) -> HashSet<EthAddress> {
+        self.non_optional_call_infos()
+            .flat_map(CallInfo::iter)
+            .flat_map(|call_info| &call_info.execution.l2_to_l1_messages)
+            .map(|message| message.message.to_address)
+            .collect()
+    }
 }
+
+/// Adds the given count to the entry of the given depth, extending the vector if needed.
+fn add_at_depth(counts_by_depth: &mut Vec<usize>, depth: usize, count: usize) {
+    if counts_by_depth.len() <= depth {
+        counts_by_depth.resize(depth + 1, 0);
+    }
+    counts_by_depth[depth] += count;
+}
+
+/// Returns the number of leading transactions whose merged summary costs at most `max_cost`,
+/// according to the given weights (i.e., greedily packs transactions into a budget). As summaries
+/// are merged, resources shared between transactions (e.g., storage entries) are counted once.
+pub fn pack_transactions(
+    infos: &[TransactionExecutionInfo],
+    budget: &SummaryWeights,
+    max_cost: u128,
+) -> usize {
+    let mut merged_summary = ExecutionSummary::default();
+    for (n_packed_txs, info) in infos.iter().enumerate() {
+        merged_summary = merged_summary + info.summarize();
+        if merged_summary.weighted_cost(budget) > max_cost {
+            return n_packed_txs;
+        }
+    }
+
+    infos.len()
+}
+
+/// Summarizes the given transactions concurrently, each on a blocking thread, so that the async
+/// runtime is not stalled by the CPU-heavy work. The summaries are returned in input order.
+#[cfg(feature = "summarize_async")]
+pub async fn summarize_all(infos: Vec<TransactionExecutionInfo>) -> Vec<ExecutionSummary> {
+    let tasks: Vec<_> = infos
+        .into_iter()
+        .map(|info| tokio::task::spawn_blocking(move || info.summarize()))
+        .collect();
+
+    let mut summaries = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        summaries.push(task.await.expect("Summarization task should not panic."));
+    }
+    summaries
+}
+
 pub trait ExecutionResourcesTraits {
     fn total_n_steps(&self) -> usize;
     fn prover_builtins(&self) -> HashMap<BuiltinName, usize>;
@@ -1,21 +1,63 @@
+use std::collections::{HashMap, HashSet};
+
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "transaction_serde")]
+use assert_matches::assert_matches;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
 use rstest::rstest;
-use starknet_api::core::{ClassHash, ContractAddress, EthAddress, PatriciaKey};
+use starknet_api::contract_class::EntryPointType;
+use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, EthAddress, PatriciaKey};
+use starknet_api::hash::Keccak;
 use starknet_api::state::StorageKey;
-use starknet_api::transaction::L2ToL1Payload;
+use starknet_api::transaction::{
+    EventContent,
+    EventData,
+    EventKey,
+    L2ToL1Payload,
+    TransactionHash,
+};
 use starknet_api::{class_hash, felt, patricia_key};
 use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::Poseidon;
 
+#[cfg(feature = "transaction_serde")]
+use crate::execution::call_info::CALL_INFO_ENCODING_VERSION;
 use crate::execution::call_info::{
+    Bloom,
     CallExecution,
     CallInfo,
     EventSummary,
     ExecutionSummary,
+    ExecutionSummaryBuilder,
+    FeeInput,
     MessageToL1,
     OrderedEvent,
     OrderedL2ToL1Message,
+    PortableSummary,
+    ProtoExecutionSummary,
+    SummaryAccumulator,
+    SummaryWeights,
+    TreeDiff,
+    TreeDiffKind,
 };
 use crate::execution::entry_point::CallEntryPoint;
-use crate::transaction::objects::TransactionExecutionInfo;
+#[cfg(feature = "transaction_serde")]
+use crate::execution::errors::DecodeError;
+use crate::execution::errors::{
+    PayloadBudgetError,
+    SummaryConsistencyError,
+    SummaryConversionError,
+    WalkLimitError,
+};
+#[cfg(feature = "summarize_async")]
+use crate::transaction::objects::summarize_all;
+use crate::transaction::objects::{
+    pack_transactions,
+    Phase,
+    SummaryWarning,
+    TransactionExecutionInfo,
+};
 
 #[derive(Debug, Default)]
 pub struct TestExecutionSummary {
@@ -87,6 +129,16 @@ fn call_info_with_x_events(n_events: usize, n_inner_calls: usize) -> CallInfo {
     }
 }
 
+fn call_info_with_selector(selector: &str) -> CallInfo {
+    CallInfo {
+        call: CallEntryPoint {
+            entry_point_selector: EntryPointSelector(felt!(selector)),
+            ..shared_call_info().call
+        },
+        ..Default::default()
+    }
+}
+
 fn call_info_with_deep_inner_calls(
     n_events: usize,
     n_inner_calls: usize,
@@ -109,6 +161,23 @@ fn call_info_with_deep_inner_calls(
     }
 }
 
+#[cfg(feature = "transaction_serde")]
+#[test]
+fn test_call_info_encoding_round_trip() {
+    let call_info = call_info_with_deep_inner_calls(2, 3, 1, 2);
+    let encoded = call_info.encode();
+    assert_eq!(CallInfo::decode(&encoded).unwrap(), call_info);
+    assert!(encoded.len() < serde_json::to_vec(&call_info).unwrap().len());
+
+    let mut unsupported_encoding = encoded;
+    unsupported_encoding[0] = CALL_INFO_ENCODING_VERSION + 1;
+    assert_matches!(
+        CallInfo::decode(&unsupported_encoding),
+        Err(DecodeError::UnsupportedVersion { version }) if version == CALL_INFO_ENCODING_VERSION + 1
+    );
+    assert_matches!(CallInfo::decode(&[]), Err(DecodeError::MissingVersion));
+}
+
 #[rstest]
 #[case(0, 0)]
 #[case(0, 2)]
@@ -217,6 +286,7 @@ fn test_summarize(
             total_event_keys: 0,
             total_event_data_size: 0,
         },
+        ..Default::default()
     };
 
     // Call the summarize method
@@ -227,4 +297,1435 @@ fn test_summarize(
     assert_eq!(actual_summary.visited_storage_entries, expected_summary.visited_storage_entries);
     assert_eq!(actual_summary.event_summary, expected_summary.event_summary);
     assert_eq!(actual_summary.l2_to_l1_payload_lengths, expected_summary.l2_to_l1_payload_lengths);
+    for params in [validate_params, execute_params, fee_transfer_params] {
+        assert_eq!(
+            tx_execution_info.messages_by_contract()[&params.storage_address],
+            params.num_of_messages
+        );
+    }
+
+    assert_eq!(
+        tx_execution_info.access_list(),
+        vec![
+            (validate_params.storage_address, vec![validate_params.storage_key]),
+            (execute_params.storage_address, vec![execute_params.storage_key]),
+            (fee_transfer_params.storage_address, vec![fee_transfer_params.storage_key]),
+        ]
+    );
+
+    let report = actual_summary.report();
+    for expected_line in [
+        "classes:         3",
+        "storage entries: 3",
+        "events:          6",
+        "event keys:      0",
+        "messages:        9",
+        "payload bytes:   288",
+    ] {
+        assert!(
+            report.lines().any(|line| line == expected_line),
+            "Missing {expected_line:?} in:\n{report}"
+        );
+    }
+
+    let mut csv_writer = csv::Writer::from_writer(vec![]);
+    csv_writer.serialize(actual_summary.to_record()).unwrap();
+    let csv = String::from_utf8(csv_writer.into_inner().unwrap()).unwrap();
+    assert_eq!(
+        csv.lines().collect::<Vec<_>>(),
+        vec![
+            "n_executed_classes,n_visited_storage_entries,n_messages,total_payload_length,\
+             n_events,total_event_keys,total_event_data_size,peak_per_call_storage_entries,\
+             constructor_call_count,regular_call_count,empty_event_count,min_event_order,\
+             max_event_order,total_calls,max_single_event_data",
+            "3,3,9,9,6,0,0,1,0,3,6,0,0,3,0",
+        ]
+    );
+}
+
+#[test]
+fn test_fee_preview() {
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(
+            TestExecutionSummary::new(1, 2, class_hash!("0x1"), "0x1", "0x1").to_call_info(),
+        ),
+        execute_call_info: Some(
+            TestExecutionSummary::new(2, 3, class_hash!("0x2"), "0x2", "0x2").to_call_info(),
+        ),
+        ..Default::default()
+    };
+    let weights = SummaryWeights {
+        event: 1,
+        event_key: 2,
+        event_data_felt: 3,
+        message: 4,
+        message_payload_felt: 5,
+        storage_entry: 6,
+        class_hash: 7,
+    };
+
+    let fee_breakdown = tx_execution_info.fee_preview(&weights);
+
+    // 3 events without keys or data.
+    assert_eq!(fee_breakdown.event_cost, 3);
+    // 5 messages with a single payload felt each.
+    assert_eq!(fee_breakdown.message_cost, 5 * 4 + 5 * 5);
+    assert_eq!(fee_breakdown.storage_cost, 2 * 6);
+    assert_eq!(fee_breakdown.class_cost, 2 * 7);
+    assert_eq!(
+        fee_breakdown.total,
+        fee_breakdown.event_cost
+            + fee_breakdown.message_cost
+            + fee_breakdown.storage_cost
+            + fee_breakdown.class_cost
+    );
+    assert_eq!(fee_breakdown.total, tx_execution_info.summarize().weighted_cost(&weights));
+}
+
+#[test]
+fn test_event_cost() {
+    let summary = ExecutionSummary {
+        event_summary: EventSummary { n_events: 2, total_event_keys: 3, total_event_data_size: 4 },
+        l2_to_l1_payload_lengths: vec![5],
+        executed_class_hashes: HashSet::from([class_hash!("0x1")]),
+        ..Default::default()
+    };
+    let weights = SummaryWeights {
+        event: 1,
+        event_key: 10,
+        event_data_felt: 100,
+        message: 1000,
+        message_payload_felt: 10000,
+        storage_entry: 100000,
+        class_hash: 1000000,
+    };
+
+    assert_eq!(summary.event_cost(&weights), 2 + 3 * 10 + 4 * 100);
+    assert_eq!(summary.event_cost(&weights), summary.fee_breakdown(&weights).event_cost);
+    assert_eq!(
+        summary.weighted_cost(&weights) - summary.event_cost(&weights),
+        1000 + 5 * 10000 + 1000000
+    );
+}
+
+#[test]
+fn test_fee_input_from_summary() {
+    // Each count is distinct, so that every field is traced to its source.
+    let summary = ExecutionSummary {
+        executed_class_hashes: HashSet::from([class_hash!("0x1")]),
+        visited_storage_entries: HashSet::from([
+            (ContractAddress(patricia_key!("0x1")), StorageKey(patricia_key!("0x11"))),
+            (ContractAddress(patricia_key!("0x1")), StorageKey(patricia_key!("0x12"))),
+        ]),
+        l2_to_l1_payload_lengths: vec![4, 5, 6],
+        event_summary: EventSummary { n_events: 7, total_event_keys: 8, total_event_data_size: 9 },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        FeeInput::from(&summary),
+        FeeInput {
+            n_events: 7,
+            n_event_keys: 8,
+            n_event_data_felts: 9,
+            n_messages: 3,
+            n_message_payload_felts: 15,
+            n_storage_entries: 2,
+            n_class_hashes: 1,
+        }
+    );
+}
+
+#[test]
+fn test_execution_summary_from_call_infos() {
+    let params = [
+        TestExecutionSummary::new(1, 2, class_hash!("0x1"), "0x1", "0x1"),
+        TestExecutionSummary::new(2, 3, class_hash!("0x2"), "0x2", "0x2"),
+        TestExecutionSummary::new(3, 4, class_hash!("0x3"), "0x3", "0x3"),
+    ];
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(params[0].to_call_info()),
+        execute_call_info: Some(params[1].to_call_info()),
+        fee_transfer_call_info: Some(params[2].to_call_info()),
+        ..Default::default()
+    };
+
+    let collected_summary: ExecutionSummary =
+        params.iter().map(TestExecutionSummary::to_call_info).collect();
+    assert_eq!(collected_summary, tx_execution_info.summarize());
+
+    let expected_summary = ExecutionSummary::from_parts(
+        params.iter().map(|param| param.class_hash).collect(),
+        params.iter().map(|param| (param.storage_address, param.storage_key)).collect(),
+        vec![1; 2 + 3 + 4],
+        EventSummary { n_events: 1 + 2 + 3, ..Default::default() },
+    );
+    assert_eq!(collected_summary.executed_class_hashes, expected_summary.executed_class_hashes);
+    assert_eq!(collected_summary.visited_storage_entries, expected_summary.visited_storage_entries);
+    assert_eq!(
+        collected_summary.l2_to_l1_payload_lengths,
+        expected_summary.l2_to_l1_payload_lengths
+    );
+    assert_eq!(collected_summary.event_summary, expected_summary.event_summary);
+}
+
+#[test]
+fn test_read_only_entries() {
+    // The first contract writes one of its two keys; the second only reads its key.
+    let written_call_info = CallInfo {
+        written_storage_keys: HashSet::from([StorageKey(patricia_key!("0x1"))]),
+        ..TestExecutionSummary::new(0, 0, class_hash!("0x1"), "0x1", "0x1").to_call_info()
+    };
+    let mut call_info =
+        TestExecutionSummary::new(0, 0, class_hash!("0x1"), "0x1", "0x2").to_call_info();
+    call_info.inner_calls = vec![
+        written_call_info,
+        TestExecutionSummary::new(0, 0, class_hash!("0x1"), "0x2", "0x1").to_call_info(),
+    ];
+    let summary = call_info.summarize();
+
+    assert_eq!(
+        summary.read_only_entries(),
+        HashSet::from([
+            (ContractAddress(patricia_key!("0x1")), StorageKey(patricia_key!("0x2"))),
+            (ContractAddress(patricia_key!("0x2")), StorageKey(patricia_key!("0x1"))),
+        ])
+    );
+}
+
+#[test]
+fn test_merge_execute_trees() {
+    let infos: Vec<TransactionExecutionInfo> = (1..=2)
+        .map(|n_events| TransactionExecutionInfo {
+            validate_call_info: Some(call_info_with_x_events(1, 0)),
+            execute_call_info: Some(call_info_with_x_events(n_events, 0)),
+            ..Default::default()
+        })
+        .collect();
+
+    let merged_call_info = TransactionExecutionInfo::merge_execute_trees(&infos);
+    assert_eq!(merged_call_info.inner_calls.len(), 2);
+    assert_eq!(merged_call_info.summarize().event_summary.n_events, 3);
+}
+
+#[test]
+fn test_summarize_nonempty_messages() {
+    let empty_payload_call_info = CallInfo {
+        execution: CallExecution {
+            l2_to_l1_messages: vec![OrderedL2ToL1Message::default()],
+            ..Default::default()
+        },
+        ..TestExecutionSummary::new(0, 0, class_hash!("0x1"), "0x1", "0x11").to_call_info()
+    };
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(empty_payload_call_info),
+        execute_call_info: Some(
+            TestExecutionSummary::new(0, 2, class_hash!("0x2"), "0x2", "0x12").to_call_info(),
+        ),
+        ..Default::default()
+    };
+
+    let summary = tx_execution_info.summarize_nonempty_messages();
+    assert_eq!(summary.l2_to_l1_payload_lengths, vec![1, 1]);
+    assert!(TransactionExecutionInfo {
+        execute_call_info: tx_execution_info.validate_call_info,
+        ..Default::default()
+    }
+    .summarize_nonempty_messages()
+    .l2_to_l1_payload_lengths
+    .is_empty());
+}
+
+#[test]
+fn test_validation_summary() {
+    let validate_call_info =
+        TestExecutionSummary::new(1, 2, class_hash!("0x1"), "0x1", "0x11").to_call_info();
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(validate_call_info.clone()),
+        ..Default::default()
+    };
+
+    assert_eq!(tx_execution_info.validation_summary(), Some(validate_call_info.summarize()));
+    assert_eq!(TransactionExecutionInfo::default().validation_summary(), None);
+}
+
+#[test]
+fn test_sorted_storage_entries() {
+    let storage_entry = |address: &str, key: &str| {
+        (ContractAddress(patricia_key!(address)), StorageKey(patricia_key!(key)))
+    };
+    let visited_storage_entries = HashSet::from([
+        storage_entry("0x2", "0x1"),
+        storage_entry("0x1", "0x3"),
+        storage_entry("0x1", "0x2"),
+    ]);
+    let summary = ExecutionSummary { visited_storage_entries, ..Default::default() };
+
+    assert_eq!(
+        summary.sorted_storage_entries(),
+        vec![storage_entry("0x1", "0x2"), storage_entry("0x1", "0x3"), storage_entry("0x2", "0x1")]
+    );
+}
+
+#[test]
+fn test_events_by_depth() {
+    // 2 events at depth 0, none at depth 1 and a single event in each of the 3 calls at depth 2.
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(call_info_with_deep_inner_calls(2, 1, 0, 3)),
+        ..Default::default()
+    };
+
+    assert_eq!(tx_execution_info.events_by_depth(), vec![2, 0, 3]);
+}
+
+#[test]
+fn test_storage_entries_by_depth() {
+    // 2 storage keys at depth 0, and a single key in each of the 2 calls at depth 1.
+    let inner_call = TestExecutionSummary::new(0, 0, class_hash!("0x2"), "0x2", "0x1");
+    let call_info = CallInfo {
+        inner_calls: vec![inner_call.to_call_info(), inner_call.to_call_info()],
+        accessed_storage_keys: HashSet::from([
+            StorageKey(patricia_key!("0x1")),
+            StorageKey(patricia_key!("0x2")),
+        ]),
+        ..shared_call_info()
+    };
+    let tx_execution_info =
+        TransactionExecutionInfo { execute_call_info: Some(call_info), ..Default::default() };
+
+    assert_eq!(tx_execution_info.storage_entries_by_depth(), vec![2, 2]);
+}
+
+#[test]
+fn test_assert_matches() {
+    let summary = call_info_with_x_events(2, 1).summarize();
+    assert_eq!(summary.assert_matches(&summary.clone()), Ok(()));
+
+    let expected_summary = ExecutionSummary { empty_event_count: 5, ..summary.clone() };
+    let message = summary.assert_matches(&expected_summary).unwrap_err();
+    assert_eq!(
+        message,
+        "Execution summaries differ in:\nempty_event_count: expected 5, got 3".to_string()
+    );
+}
+
+#[test]
+fn test_execution_summary_builder() {
+    let event_summary = EventSummary { n_events: 3, total_event_keys: 0, total_event_data_size: 4 };
+    let summary = ExecutionSummaryBuilder::new()
+        .l2_to_l1_payload_lengths(vec![1, 2])
+        .n_messages(2)
+        .event_summary(event_summary.clone())
+        .max_single_event_data(2)
+        .build()
+        .unwrap();
+    assert_eq!(summary.l2_to_l1_payload_lengths, vec![1, 2]);
+    assert_eq!(summary.event_summary, event_summary);
+
+    assert_eq!(
+        ExecutionSummaryBuilder::new().l2_to_l1_payload_lengths(vec![1, 2]).n_messages(3).build(),
+        Err(SummaryConsistencyError::MessageCountMismatch { n_messages: 3, n_payload_lengths: 2 })
+    );
+    assert_eq!(
+        ExecutionSummaryBuilder::new().event_summary(event_summary).empty_event_count(4).build(),
+        Err(SummaryConsistencyError::TooManyEmptyEvents { empty_event_count: 4, n_events: 3 })
+    );
+}
+
+#[test]
+fn test_eq_ignoring_message_order() {
+    let summary = ExecutionSummary { l2_to_l1_payload_lengths: vec![1, 2], ..Default::default() };
+    let reordered_summary =
+        ExecutionSummary { l2_to_l1_payload_lengths: vec![2, 1], ..Default::default() };
+
+    assert_ne!(summary, reordered_summary);
+    assert!(summary.eq_ignoring_message_order(&reordered_summary));
+}
+
+#[test]
+fn test_total_event_felts() {
+    let event = |n_keys: u8, n_data_felts: u8| OrderedEvent {
+        order: 0,
+        event: EventContent {
+            keys: (0..n_keys).map(|key| EventKey(Felt::from(key))).collect(),
+            data: EventData((0..n_data_felts).map(Felt::from).collect()),
+        },
+    };
+    let call_info = CallInfo {
+        execution: CallExecution {
+            events: vec![event(2, 3), event(1, 0), event(0, 4)],
+            ..Default::default()
+        },
+        ..shared_call_info()
+    };
+
+    assert_eq!(call_info.summarize().total_event_felts(), 10);
+    assert_eq!(ExecutionSummary::default().total_event_felts(), 0);
+}
+
+#[test]
+fn test_da_size_bytes() {
+    let mut execute_call_info =
+        TestExecutionSummary::new(0, 3, class_hash!("0x1"), "0x1", "0x11").to_call_info();
+    execute_call_info.written_storage_keys = HashSet::from([StorageKey(patricia_key!("0x11"))]);
+    execute_call_info.execution.events = vec![OrderedEvent {
+        order: 0,
+        event: EventContent {
+            keys: vec![EventKey(felt!("0x1"))],
+            data: EventData(vec![felt!("0x2"), felt!("0x3")]),
+        },
+    }];
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(
+            TestExecutionSummary::new(0, 1, class_hash!("0x2"), "0x2", "0x12").to_call_info(),
+        ),
+        execute_call_info: Some(execute_call_info),
+        ..Default::default()
+    };
+
+    // A written storage entry (the validation only reads its entry), 3 event felts and 4 message
+    // payload felts.
+    assert_eq!(tx_execution_info.summarize().da_size_bytes(32), 8 * 32);
+}
+
+#[test]
+fn test_contract_bloom() {
+    let summary_of = |contract_address: &str| {
+        TestExecutionSummary::new(0, 1, class_hash!("0x1"), contract_address, "0x11")
+            .to_call_info()
+            .summarize()
+    };
+    let bloom = summary_of("0x1").contract_bloom();
+
+    assert!(bloom.may_contain(ContractAddress(patricia_key!("0x1"))));
+    assert!(!bloom.may_intersect(&summary_of("0x2").contract_bloom()));
+    assert!(!bloom.may_intersect(&Bloom::default()));
+    // Summaries sharing a contract always intersect.
+    let shared_summary = summary_of("0x2") + summary_of("0x1");
+    assert!(bloom.may_intersect(&shared_summary.contract_bloom()));
+}
+
+#[test]
+fn test_all_event_key_felts() {
+    let ordered_event = |order: usize, keys: Vec<Felt>| OrderedEvent {
+        order,
+        event: EventContent {
+            keys: keys.into_iter().map(EventKey).collect(),
+            ..Default::default()
+        },
+    };
+    // The first event is emitted by the inner call, so call-tree order differs from event order.
+    let inner_call = CallInfo {
+        execution: CallExecution {
+            events: vec![ordered_event(0, vec![felt!("0x1")])],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let execute_call_info = CallInfo {
+        execution: CallExecution {
+            events: vec![ordered_event(1, vec![felt!("0x2"), felt!("0x3")])],
+            ..Default::default()
+        },
+        inner_calls: vec![inner_call],
+        ..Default::default()
+    };
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(execute_call_info),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        tx_execution_info.all_event_key_felts(),
+        vec![felt!("0x1"), felt!("0x2"), felt!("0x3")]
+    );
+}
+
+#[test]
+fn test_try_walk_calls_halts_on_first_error() {
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(call_info_with_x_events(0, 3)),
+        ..Default::default()
+    };
+    let mut n_visited_calls = 0;
+    let result = tx_execution_info.try_walk_calls(|_call_info, depth| {
+        n_visited_calls += 1;
+        if n_visited_calls == 2 {
+            Err(depth)
+        } else {
+            Ok(())
+        }
+    });
+
+    // The second visited call is the first inner call; the remaining inner calls are skipped.
+    assert_eq!(result, Err(1));
+    assert_eq!(n_visited_calls, 2);
+
+    let mut n_walked_calls = 0;
+    tx_execution_info.walk_calls(|_call_info, _depth| n_walked_calls += 1);
+    assert_eq!(n_walked_calls, 4);
+}
+
+#[test]
+fn test_enumerate_calls() {
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info_with_x_events(0, 1)),
+        execute_call_info: Some(call_info_with_deep_inner_calls(0, 2, 0, 2)),
+        ..Default::default()
+    };
+
+    let enumerated_calls = tx_execution_info.enumerate_calls();
+    let indices: Vec<usize> = enumerated_calls.iter().map(|(index, _)| *index).collect();
+    assert_eq!(indices, (0..9).collect::<Vec<_>>());
+
+    let mut walked_calls = Vec::new();
+    tx_execution_info.walk_calls(|call_info, _depth| walked_calls.push(call_info));
+    for ((_, enumerated_call), walked_call) in enumerated_calls.into_iter().zip(walked_calls) {
+        assert!(std::ptr::eq(enumerated_call, walked_call));
+    }
+}
+
+#[test]
+fn test_walk_calls_bounded() {
+    // A root call with 9 inner calls.
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(call_info_with_x_events(0, 9)),
+        ..Default::default()
+    };
+
+    let mut n_visited_calls = 0;
+    let result = tx_execution_info.walk_calls_bounded(5, |_call_info, _depth| n_visited_calls += 1);
+    assert_eq!(result, Err(WalkLimitError::LimitExceeded { max_nodes: 5 }));
+    assert_eq!(n_visited_calls, 5);
+
+    let mut n_visited_calls = 0;
+    let result =
+        tx_execution_info.walk_calls_bounded(10, |_call_info, _depth| n_visited_calls += 1);
+    assert_eq!(result, Ok(()));
+    assert_eq!(n_visited_calls, 10);
+}
+
+#[test]
+fn test_messages_by_destination() {
+    let message = |to_address: u64, payload: u64| MessageToL1 {
+        to_address: EthAddress::try_from(Felt::from(to_address)).unwrap(),
+        payload: L2ToL1Payload(vec![Felt::from(payload)]),
+    };
+    let ordered_message = |order: usize, to_address: u64, payload: u64| OrderedL2ToL1Message {
+        order,
+        message: message(to_address, payload),
+    };
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(CallInfo {
+            execution: CallExecution {
+                l2_to_l1_messages: vec![
+                    ordered_message(0, 1, 10),
+                    ordered_message(1, 2, 20),
+                    ordered_message(2, 1, 30),
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let expected_messages_1 = [message(1, 10), message(1, 30)];
+    let expected_messages_2 = [message(2, 20)];
+    let expected_grouping: HashMap<EthAddress, Vec<&MessageToL1>> = HashMap::from([
+        (expected_messages_1[0].to_address, expected_messages_1.iter().collect()),
+        (expected_messages_2[0].to_address, expected_messages_2.iter().collect()),
+    ]);
+    assert_eq!(tx_execution_info.messages_by_destination(), expected_grouping);
+}
+
+#[test]
+fn test_message_destinations() {
+    let ordered_message = |order: usize, to_address: u64| OrderedL2ToL1Message {
+        order,
+        message: MessageToL1 {
+            to_address: EthAddress::try_from(Felt::from(to_address)).unwrap(),
+            payload: L2ToL1Payload::default(),
+        },
+    };
+    let call_info = CallInfo {
+        execution: CallExecution {
+            l2_to_l1_messages: vec![
+                ordered_message(0, 1),
+                ordered_message(1, 2),
+                ordered_message(2, 1),
+            ],
+            ..Default::default()
+        },
+        ..shared_call_info()
+    };
+    let tx_execution_info =
+        TransactionExecutionInfo { execute_call_info: Some(call_info), ..Default::default() };
+
+    assert_eq!(
+        tx_execution_info.message_destinations(),
+        HashSet::from([
+            EthAddress::try_from(Felt::from(1_u64)).unwrap(),
+            EthAddress::try_from(Felt::from(2_u64)).unwrap(),
+        ])
+    );
+}
+
+#[test]
+fn test_peak_per_call_storage_entries() {
+    let call_info_with_storage_keys = |storage_keys: &[&str]| CallInfo {
+        accessed_storage_keys: storage_keys
+            .iter()
+            .map(|storage_key| StorageKey(patricia_key!(*storage_key)))
+            .collect(),
+        ..shared_call_info()
+    };
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info_with_storage_keys(&["0x1"])),
+        execute_call_info: Some(CallInfo {
+            inner_calls: vec![call_info_with_storage_keys(&["0x1", "0x2", "0x3"])],
+            ..call_info_with_storage_keys(&["0x4"])
+        }),
+        ..Default::default()
+    };
+
+    assert_eq!(tx_execution_info.summarize().peak_per_call_storage_entries, 3);
+}
+
+#[test]
+fn test_portable_summary() {
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(
+            TestExecutionSummary::new(1, 2, class_hash!("0x2"), "0x2", "0x12").to_call_info(),
+        ),
+        execute_call_info: Some(
+            TestExecutionSummary::new(3, 1, class_hash!("0x1"), "0x1", "0x11").to_call_info(),
+        ),
+        ..Default::default()
+    };
+
+    let expected_portable_summary = PortableSummary {
+        executed_class_hashes: vec![class_hash!("0x1"), class_hash!("0x2")],
+        visited_storage_entries: vec![
+            (ContractAddress(patricia_key!("0x1")), StorageKey(patricia_key!("0x11"))),
+            (ContractAddress(patricia_key!("0x2")), StorageKey(patricia_key!("0x12"))),
+        ],
+        l2_to_l1_payload_lengths: vec![1, 1, 1],
+        n_events: 4,
+        peak_per_call_storage_entries: 1,
+        regular_call_count: 2,
+        empty_event_count: 4,
+        event_order_range: Some((0, 0)),
+        total_calls: 2,
+        ..Default::default()
+    };
+    let portable_summary = tx_execution_info.summarize().to_portable();
+    assert_eq!(portable_summary, expected_portable_summary);
+
+    let serialized = serde_json::to_string(&portable_summary).unwrap();
+    assert_eq!(serde_json::from_str::<PortableSummary>(&serialized).unwrap(), portable_summary);
+}
+
+#[test]
+fn test_suspicious_calls() {
+    // Built from defaults, so the inner call has no class hash.
+    let suspicious_call = CallInfo {
+        execution: CallExecution { events: vec![OrderedEvent::default()], ..Default::default() },
+        ..Default::default()
+    };
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(CallInfo {
+            inner_calls: vec![suspicious_call.clone()],
+            ..call_info_with_x_events(1, 0)
+        }),
+        ..Default::default()
+    };
+
+    assert_eq!(tx_execution_info.suspicious_calls(), vec![&suspicious_call]);
+}
+
+#[test]
+fn test_calls_exceeding_event_limit() {
+    let noisy_call = call_info_with_x_events(5, 0);
+    // The root emits no events itself, so it's within the limit although its subtree isn't.
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(CallInfo {
+            inner_calls: vec![noisy_call.clone(), call_info_with_x_events(2, 0)],
+            ..call_info_with_x_events(0, 0)
+        }),
+        ..Default::default()
+    };
+
+    assert_eq!(tx_execution_info.calls_exceeding_event_limit(3), vec![&noisy_call]);
+}
+
+#[test]
+fn test_effectless_calls() {
+    let effectless_call = shared_call_info();
+    // The root only accesses storage, so it's effectful.
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(CallInfo {
+            inner_calls: vec![effectless_call.clone()],
+            ..TestExecutionSummary::new(0, 0, class_hash!("0x2"), "0x2", "0x2").to_call_info()
+        }),
+        ..Default::default()
+    };
+
+    assert_eq!(tx_execution_info.effectless_calls(), vec![&effectless_call]);
+}
+
+#[test]
+fn test_selector_call_counts() {
+    // Calls without a class hash are skipped.
+    let mut call_info_without_class_hash = call_info_with_selector("0x3");
+    call_info_without_class_hash.call.class_hash = None;
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(CallInfo {
+            inner_calls: vec![
+                call_info_with_selector("0x2"),
+                call_info_with_selector("0x3"),
+                call_info_without_class_hash,
+            ],
+            ..call_info_with_selector("0x2")
+        }),
+        ..Default::default()
+    };
+
+    let class_hash = class_hash!("0x1");
+    let expected_selector_call_counts =
+        HashMap::from([((class_hash, felt!("0x2")), 2), ((class_hash, felt!("0x3")), 1)]);
+    assert_eq!(tx_execution_info.selector_call_counts(), expected_selector_call_counts);
+}
+
+#[test]
+fn test_distinct_selectors() {
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(CallInfo {
+            inner_calls: vec![call_info_with_selector("0x2"), call_info_with_selector("0x3")],
+            ..call_info_with_selector("0x2")
+        }),
+        ..Default::default()
+    };
+
+    assert_eq!(tx_execution_info.distinct_selectors().len(), 2);
+}
+
+#[test]
+fn test_proto_execution_summary_round_trip() {
+    let call_info = CallInfo {
+        inner_calls: vec![
+            TestExecutionSummary::new(2, 3, class_hash!("0x2"), "0x2", "0x22").to_call_info()
+        ],
+        ..TestExecutionSummary::new(1, 2, class_hash!("0x1"), "0x1", "0x11").to_call_info()
+    };
+    let summary = call_info.summarize();
+
+    let proto_summary = ProtoExecutionSummary::from(summary.clone());
+    assert_eq!(proto_summary.visited_storage_addresses.len(), 2);
+    assert_eq!(ExecutionSummary::try_from(proto_summary).unwrap(), summary);
+}
+
+#[test]
+fn test_proto_execution_summary_inconsistent_event_order_range() {
+    let proto_summary = ProtoExecutionSummary::from(call_info_with_x_events(2, 0).summarize());
+
+    for (min_event_order, max_event_order) in [(Some(0), None), (None, Some(1)), (Some(2), Some(1))]
+    {
+        let result = ExecutionSummary::try_from(ProtoExecutionSummary {
+            min_event_order,
+            max_event_order,
+            ..proto_summary.clone()
+        });
+        assert_matches!(
+            result,
+            Err(SummaryConversionError::InconsistentEventOrderRange {
+                min_event_order: min,
+                max_event_order: max,
+            }) if (min, max) == (min_event_order, max_event_order)
+        );
+    }
+}
+
+#[test]
+fn test_summarize_counts_identical_siblings() {
+    // Identical sibling calls are distinct calls (e.g., the same call made twice).
+    let subtree = call_info_with_x_events(2, 1);
+    let call_info = CallInfo { inner_calls: vec![subtree.clone(), subtree], ..shared_call_info() };
+
+    assert_eq!(call_info.summarize().event_summary.n_events, 6);
+}
+
+#[test]
+fn test_summarize_lossy() {
+    let valid_call_info =
+        TestExecutionSummary::new(1, 0, class_hash!("0x1"), "0x1", "0x11").to_call_info();
+    let mut malformed_call_info =
+        TestExecutionSummary::new(2, 0, class_hash!("0x2"), "0x2", "0x12").to_call_info();
+    malformed_call_info.call.class_hash = None;
+    malformed_call_info.execution.events[0].order = 1;
+    let malformed_address = malformed_call_info.call.storage_address;
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(valid_call_info),
+        execute_call_info: Some(malformed_call_info),
+        ..Default::default()
+    };
+
+    let (summary, warnings) = tx_execution_info.summarize_lossy();
+    // The malformed call's effects are kept; only its class hash is missing.
+    assert_eq!(summary.executed_class_hashes, HashSet::from([class_hash!("0x1")]));
+    assert_eq!(
+        summary.visited_storage_entries,
+        HashSet::from([
+            (ContractAddress(patricia_key!("0x1")), StorageKey(patricia_key!("0x11"))),
+            (ContractAddress(patricia_key!("0x2")), StorageKey(patricia_key!("0x12"))),
+        ])
+    );
+    assert_eq!(summary.event_summary.n_events, 3);
+    assert_eq!(summary.total_calls, 2);
+    assert_eq!(
+        warnings,
+        vec![
+            SummaryWarning::OutOfOrderEvents { storage_address: malformed_address },
+            SummaryWarning::MissingClassHash { storage_address: malformed_address },
+        ]
+    );
+}
+
+#[test]
+fn test_summarize_filtered() {
+    let mut excluded_call_info =
+        TestExecutionSummary::new(1, 0, class_hash!("0x1"), "0x1", "0x11").to_call_info();
+    excluded_call_info
+        .inner_calls
+        .push(TestExecutionSummary::new(2, 0, class_hash!("0x2"), "0x2", "0x12").to_call_info());
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(
+            TestExecutionSummary::new(4, 0, class_hash!("0x1"), "0x1", "0x13").to_call_info(),
+        ),
+        execute_call_info: Some(excluded_call_info),
+        fee_transfer_call_info: Some(
+            TestExecutionSummary::new(3, 0, class_hash!("0x3"), "0x3", "0x14").to_call_info(),
+        ),
+        ..Default::default()
+    };
+
+    let summary = tx_execution_info
+        .summarize_filtered(|call_info| call_info.call.class_hash != Some(class_hash!("0x1")));
+    assert_eq!(
+        summary.executed_class_hashes,
+        HashSet::from([class_hash!("0x2"), class_hash!("0x3")])
+    );
+    assert_eq!(summary.event_summary.n_events, 5);
+    assert_eq!(tx_execution_info.summarize_filtered(|_| true), tx_execution_info.summarize());
+}
+
+#[test]
+fn test_summarize_with_revert_semantics() {
+    // The reverted call read a storage key, wrote another, and read and wrote a third, and
+    // emitted events and sent messages, as did its inner call.
+    let mut reverted_call_info =
+        TestExecutionSummary::new(1, 1, class_hash!("0x2"), "0x2", "0x12").to_call_info();
+    reverted_call_info.execution.failed = true;
+    let written_storage_key = StorageKey(patricia_key!("0x14"));
+    let read_modify_write_key = StorageKey(patricia_key!("0x15"));
+    reverted_call_info.accessed_storage_keys.extend([written_storage_key, read_modify_write_key]);
+    reverted_call_info.read_storage_keys.insert(read_modify_write_key);
+    reverted_call_info.written_storage_keys.extend([written_storage_key, read_modify_write_key]);
+    reverted_call_info
+        .inner_calls
+        .push(TestExecutionSummary::new(2, 2, class_hash!("0x3"), "0x3", "0x13").to_call_info());
+    let mut root_call_info =
+        TestExecutionSummary::new(3, 3, class_hash!("0x1"), "0x1", "0x11").to_call_info();
+    root_call_info.inner_calls.push(reverted_call_info);
+    let tx_execution_info =
+        TransactionExecutionInfo { execute_call_info: Some(root_call_info), ..Default::default() };
+
+    let summary = tx_execution_info.summarize_with_revert_semantics();
+    let storage_entry = |address: &str, key: &str| {
+        (ContractAddress(patricia_key!(address)), StorageKey(patricia_key!(key)))
+    };
+    assert_eq!(
+        summary.visited_storage_entries,
+        HashSet::from([
+            storage_entry("0x1", "0x11"),
+            storage_entry("0x2", "0x12"),
+            storage_entry("0x2", "0x15"),
+            storage_entry("0x3", "0x13"),
+        ])
+    );
+    assert!(summary.written_storage_entries.is_empty());
+    assert!(tx_execution_info
+        .summarize()
+        .visited_storage_entries
+        .contains(&storage_entry("0x2", "0x14")));
+    assert_eq!(summary.event_summary.n_events, 3);
+    assert_eq!(summary.l2_to_l1_payload_lengths.len(), 3);
+    assert_eq!(summary.total_calls, 3);
+}
+
+#[test]
+fn test_read_modify_write_entries() {
+    let (read_only_key, read_modify_write_key) =
+        (StorageKey(patricia_key!("0x11")), StorageKey(patricia_key!("0x12")));
+    // The inner call writes the key that its caller read.
+    let mut inner_call_info = shared_call_info();
+    inner_call_info.accessed_storage_keys.insert(read_modify_write_key);
+    inner_call_info.written_storage_keys.insert(read_modify_write_key);
+    let mut call_info = shared_call_info();
+    call_info.accessed_storage_keys.extend([read_only_key, read_modify_write_key]);
+    call_info.read_storage_keys.extend([read_only_key, read_modify_write_key]);
+    call_info.inner_calls.push(inner_call_info);
+    let tx_execution_info =
+        TransactionExecutionInfo { execute_call_info: Some(call_info), ..Default::default() };
+
+    assert_eq!(
+        tx_execution_info.read_modify_write_entries(),
+        HashSet::from([(ContractAddress::default(), read_modify_write_key)])
+    );
+}
+
+#[test]
+fn test_receipt_events_phases() {
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info_with_x_events(1, 0)),
+        execute_call_info: Some(call_info_with_x_events(1, 1)),
+        ..Default::default()
+    };
+
+    let phases: Vec<Phase> = tx_execution_info
+        .receipt_events()
+        .into_iter()
+        .map(|receipt_event| receipt_event.phase)
+        .collect();
+    assert_eq!(phases, vec![Phase::Validate, Phase::Execute, Phase::Execute]);
+}
+
+#[test]
+fn test_quick_stats_matches_summarize() {
+    let leaf_call = TestExecutionSummary::new(2, 3, class_hash!("0x3"), "0x3", "0x3");
+    let middle_call = CallInfo {
+        inner_calls: vec![leaf_call.to_call_info()],
+        ..TestExecutionSummary::new(0, 1, class_hash!("0x2"), "0x2", "0x2").to_call_info()
+    };
+    let execute_call_info = CallInfo {
+        inner_calls: vec![middle_call.clone(), middle_call],
+        ..TestExecutionSummary::new(1, 2, class_hash!("0x1"), "0x1", "0x1").to_call_info()
+    };
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info_with_deep_inner_calls(2, 2, 1, 1)),
+        execute_call_info: Some(execute_call_info),
+        fee_transfer_call_info: Some(
+            TestExecutionSummary::new(1, 1, class_hash!("0x4"), "0x4", "0x4").to_call_info(),
+        ),
+        ..Default::default()
+    };
+
+    let stats = tx_execution_info.quick_stats();
+    let summary = tx_execution_info.summarize();
+    assert_eq!(stats.n_events, summary.event_summary.n_events);
+    assert_eq!(stats.n_messages, summary.l2_to_l1_payload_lengths.len());
+    assert_eq!(stats.n_calls, summary.total_calls);
+    let storage_entries_by_depth = tx_execution_info.storage_entries_by_depth();
+    assert_eq!(stats.n_storage_accesses, storage_entries_by_depth.iter().sum::<usize>());
+    assert_eq!(stats.max_depth + 1, storage_entries_by_depth.len());
+}
+
+#[test]
+fn test_receipt_messages() {
+    let call_info = |address: &str, orders: &[usize], inner_calls: Vec<CallInfo>| CallInfo {
+        call: CallEntryPoint {
+            storage_address: ContractAddress(patricia_key!(address)),
+            ..Default::default()
+        },
+        execution: CallExecution {
+            l2_to_l1_messages: orders
+                .iter()
+                .map(|&order| OrderedL2ToL1Message {
+                    order,
+                    message: MessageToL1 {
+                        to_address: EthAddress::try_from(Felt::from(order)).unwrap(),
+                        payload: L2ToL1Payload(vec![Felt::from(order)]),
+                    },
+                })
+                .collect(),
+            ..Default::default()
+        },
+        inner_calls,
+        ..Default::default()
+    };
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info("0xa", &[7], vec![])),
+        execute_call_info: Some(call_info(
+            "0xb",
+            &[1, 3],
+            vec![call_info("0xc", &[0], vec![]), call_info("0xd", &[2], vec![])],
+        )),
+        ..Default::default()
+    };
+
+    let messages: Vec<(ContractAddress, Felt)> = tx_execution_info
+        .receipt_messages()
+        .into_iter()
+        .map(|receipt_message| {
+            assert_eq!(
+                receipt_message.to_address,
+                EthAddress::try_from(receipt_message.payload[0]).unwrap()
+            );
+            (receipt_message.from_address, receipt_message.payload[0])
+        })
+        .collect();
+    let expected_messages = [("0xa", 7_u8), ("0xc", 0), ("0xb", 1), ("0xd", 2), ("0xb", 3)]
+        .map(|(address, order)| (ContractAddress(patricia_key!(address)), Felt::from(order)));
+    assert_eq!(messages, expected_messages);
+}
+
+#[test]
+fn test_events_commitment_regression() {
+    let call_info = CallInfo {
+        call: CallEntryPoint {
+            storage_address: ContractAddress(patricia_key!("0xa")),
+            ..Default::default()
+        },
+        execution: CallExecution {
+            events: vec![OrderedEvent {
+                order: 0,
+                event: EventContent {
+                    keys: vec![EventKey(felt!("0x2")), EventKey(felt!("0x3"))],
+                    data: EventData(vec![felt!("0x4"), felt!("0x5"), felt!("0x6")]),
+                },
+            }],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let tx_execution_info =
+        TransactionExecutionInfo { execute_call_info: Some(call_info), ..Default::default() };
+
+    let transaction_hash = TransactionHash(felt!("0x1234"));
+    let expected_root = felt!("0x07da6b2a6d82adbdb843c7f3e20c8cca549e04bcb21db8f01317b1b6af217037");
+    assert_eq!(tx_execution_info.events_commitment::<Poseidon>(transaction_hash), expected_root);
+    let expected_keccak_root =
+        felt!("0x004aeabffa0f2cc4a7498d043865e044f3a6b0d1212b277a4e2b91aecb81361f");
+    assert_eq!(
+        tx_execution_info.events_commitment::<Keccak>(transaction_hash),
+        expected_keccak_root
+    );
+    assert_eq!(
+        TransactionExecutionInfo::default().events_commitment::<Poseidon>(transaction_hash),
+        Felt::ZERO
+    );
+}
+
+#[test]
+fn test_pack_transactions() {
+    let infos: Vec<TransactionExecutionInfo> = (0..3)
+        .map(|_| TransactionExecutionInfo {
+            execute_call_info: Some(call_info_with_x_events(1, 0)),
+            ..Default::default()
+        })
+        .collect();
+    let weights = SummaryWeights { event: 10, ..Default::default() };
+
+    assert_eq!(pack_transactions(&infos, &weights, 25), 2);
+    assert_eq!(pack_transactions(&infos, &weights, 30), 3);
+}
+
+// A naive recursive summarizer, merging the summaries of the inner calls into that of their parent.
+fn summarize_recursively(call_info: &CallInfo, depth: usize) -> ExecutionSummary {
+    let mut summary = ExecutionSummary::default();
+    summary.visit_call(call_info, depth);
+    call_info
+        .inner_calls
+        .iter()
+        .fold(summary, |summary, inner_call| summary + summarize_recursively(inner_call, depth + 1))
+}
+
+#[test]
+fn test_summarize_agrees_with_recursive_summarizer() {
+    for seed in 0..64 {
+        let mut bytes = [0; 4096];
+        StdRng::seed_from_u64(seed).fill_bytes(&mut bytes);
+        let call_info = CallInfo::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+
+        assert_eq!(call_info.summarize(), summarize_recursively(&call_info, 0), "Seed: {seed}.");
+    }
+}
+
+#[derive(Default)]
+struct LeafCallCounter {
+    n_leaf_calls: usize,
+}
+
+impl SummaryAccumulator for LeafCallCounter {
+    fn visit_call(&mut self, call_info: &CallInfo, _depth: usize) {
+        if call_info.inner_calls.is_empty() {
+            self.n_leaf_calls += 1;
+        }
+    }
+}
+
+#[test]
+fn test_accumulate() {
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info_with_x_events(1, 0)),
+        execute_call_info: Some(call_info_with_deep_inner_calls(1, 2, 1, 3)),
+        ..Default::default()
+    };
+
+    let mut leaf_call_counter = LeafCallCounter::default();
+    tx_execution_info.accumulate(&mut leaf_call_counter);
+    assert_eq!(leaf_call_counter.n_leaf_calls, 7);
+
+    let mut summary = ExecutionSummary::default();
+    tx_execution_info.accumulate(&mut summary);
+    assert_eq!(summary, tx_execution_info.summarize());
+}
+
+#[test]
+fn test_summarize_into_reused_buffer() {
+    let first_tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(
+            TestExecutionSummary::new(1, 2, class_hash!("0x1"), "0x1", "0x11").to_call_info(),
+        ),
+        execute_call_info: Some(call_info_with_deep_inner_calls(1, 2, 1, 1)),
+        ..Default::default()
+    };
+    let second_tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(
+            TestExecutionSummary::new(3, 1, class_hash!("0x2"), "0x2", "0x12").to_call_info(),
+        ),
+        ..Default::default()
+    };
+
+    let mut summary = ExecutionSummary::default();
+    first_tx_execution_info.summarize_into(&mut summary);
+    assert_eq!(summary, first_tx_execution_info.summarize());
+    second_tx_execution_info.summarize_into(&mut summary);
+    assert_eq!(summary, second_tx_execution_info.summarize());
+
+    summary.clear();
+    assert_eq!(summary, ExecutionSummary::default());
+}
+
+#[rstest]
+#[case::events_only(
+    TestExecutionSummary::new(2, 0, class_hash!("0x1"), "0x1", "0x11").to_call_info(),
+    true
+)]
+#[case::messages_only(
+    TestExecutionSummary::new(0, 2, class_hash!("0x1"), "0x1", "0x11").to_call_info(),
+    true
+)]
+#[case::storage_read_only(
+    TestExecutionSummary::new(0, 0, class_hash!("0x1"), "0x1", "0x11").to_call_info(),
+    false
+)]
+#[case::storage_write(
+    CallInfo {
+        written_storage_keys: HashSet::from([StorageKey(patricia_key!("0x11"))]),
+        ..TestExecutionSummary::new(0, 0, class_hash!("0x1"), "0x1", "0x11").to_call_info()
+    },
+    true
+)]
+fn test_has_external_effects(
+    #[case] execute_call_info: CallInfo,
+    #[case] expected_has_external_effects: bool,
+) {
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(execute_call_info),
+        ..Default::default()
+    };
+    assert_eq!(tx_execution_info.has_external_effects(), expected_has_external_effects);
+}
+
+#[rstest]
+#[case::under_budget(96, Ok(()))]
+#[case::over_budget(
+    95,
+    Err(PayloadBudgetError::BudgetExceeded { accumulated_bytes: 96, max_total_bytes: 95 })
+)]
+fn test_summarize_with_payload_budget(
+    #[case] max_total_bytes: usize,
+    #[case] expected_result: Result<(), PayloadBudgetError>,
+) {
+    // Three messages, each with a single-felt payload.
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(
+            TestExecutionSummary::new(0, 1, class_hash!("0x1"), "0x1", "0x11").to_call_info(),
+        ),
+        execute_call_info: Some(
+            TestExecutionSummary::new(0, 2, class_hash!("0x2"), "0x2", "0x12").to_call_info(),
+        ),
+        ..Default::default()
+    };
+
+    let result = tx_execution_info.summarize_with_payload_budget(max_total_bytes);
+    match expected_result {
+        Ok(()) => assert_eq!(result.unwrap(), tx_execution_info.summarize()),
+        Err(expected_error) => assert_eq!(result.unwrap_err(), expected_error),
+    }
+}
+
+#[test]
+fn test_event_order_range() {
+    assert_eq!(call_info_with_x_events(0, 0).summarize().event_order_range, None);
+
+    // Orders 1 and 2 in the root call, and 0 in its inner call.
+    let mut call_info = call_info_with_x_events(2, 1);
+    for (order, ordered_event) in (1..).zip(call_info.execution.events.iter_mut()) {
+        ordered_event.order = order;
+    }
+    assert_eq!(call_info.summarize().event_order_range, Some((0, 2)));
+}
+
+#[test]
+fn test_empty_event_count() {
+    assert_eq!(call_info_with_x_events(3, 0).summarize().empty_event_count, 3);
+
+    let mut call_info = call_info_with_x_events(3, 0);
+    call_info.execution.events[0].event.keys.push(EventKey(felt!("0x1")));
+    call_info.execution.events[1].event.data.0.push(felt!("0x2"));
+    assert_eq!(call_info.summarize().empty_event_count, 1);
+}
+
+#[test]
+fn test_max_single_event_data() {
+    let event_with_data = |n_data_felts: usize| OrderedEvent {
+        order: 0,
+        event: EventContent {
+            data: EventData(vec![Felt::ZERO; n_data_felts]),
+            ..Default::default()
+        },
+    };
+    let mut inner_call_info = call_info_with_x_events(0, 0);
+    inner_call_info.execution.events = vec![event_with_data(5)];
+    let mut call_info = call_info_with_x_events(0, 0);
+    call_info.execution.events = vec![event_with_data(2)];
+    call_info.inner_calls = vec![inner_call_info];
+
+    assert_eq!(call_info.summarize().max_single_event_data, 5);
+}
+
+#[test]
+fn test_class_hashes_in_order() {
+    let call_info_of_class =
+        |class_hash| TestExecutionSummary::new(0, 0, class_hash, "0x1", "0x11").to_call_info();
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info_of_class(class_hash!("0x2"))),
+        execute_call_info: Some(call_info_of_class(class_hash!("0x1"))),
+        fee_transfer_call_info: Some(call_info_of_class(class_hash!("0x2"))),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        tx_execution_info.class_hashes_in_order(),
+        vec![class_hash!("0x2"), class_hash!("0x1")]
+    );
+}
+
+#[test]
+fn test_total_calls() {
+    let (n_inner_calls, n_inner_calls_of_each_inner_call) = (2, 3);
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info_with_deep_inner_calls(
+            1,
+            n_inner_calls,
+            1,
+            n_inner_calls_of_each_inner_call,
+        )),
+        execute_call_info: Some(call_info_with_x_events(2, 1)),
+        fee_transfer_call_info: Some(call_info_with_x_events(1, 0)),
+        ..Default::default()
+    };
+
+    let n_validate_calls = 1 + n_inner_calls * (1 + n_inner_calls_of_each_inner_call);
+    assert_eq!(tx_execution_info.summarize().total_calls, n_validate_calls + 2 + 1);
+}
+
+#[test]
+fn test_class_hash_counts() {
+    // All calls share the class hash of `shared_call_info`.
+    let (n_inner_calls, n_inner_calls_of_each_inner_call) = (2, 3);
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(call_info_with_deep_inner_calls(
+            1,
+            n_inner_calls,
+            1,
+            n_inner_calls_of_each_inner_call,
+        )),
+        ..Default::default()
+    };
+    let class_hash_counts = tx_execution_info.class_hash_counts();
+
+    let n_calls = 1 + n_inner_calls * (1 + n_inner_calls_of_each_inner_call);
+    assert_eq!(class_hash_counts, HashMap::from([(class_hash!("0x1"), n_calls)]));
+    assert_eq!(
+        class_hash_counts.values().sum::<usize>(),
+        tx_execution_info.summarize().total_calls
+    );
+}
+
+#[test]
+fn test_class_hash_counts_skips_calls_without_class_hash() {
+    let mut inner_call_info = shared_call_info();
+    inner_call_info.call.class_hash = None;
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(CallInfo { inner_calls: vec![inner_call_info], ..shared_call_info() }),
+        ..Default::default()
+    };
+
+    assert_eq!(tx_execution_info.class_hash_counts(), HashMap::from([(class_hash!("0x1"), 1)]));
+}
+
+#[test]
+fn test_events_by_depth_and_contract() {
+    let call_info = |n_events: usize, storage_address: &str| {
+        TestExecutionSummary::new(n_events, 0, class_hash!("0x1"), storage_address, "0x1")
+            .to_call_info()
+    };
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info(1, "0x2")),
+        execute_call_info: Some(CallInfo {
+            inner_calls: vec![call_info(3, "0x2"), call_info(1, "0x1"), call_info(0, "0x1")],
+            ..call_info(2, "0x1")
+        }),
+        ..Default::default()
+    };
+    let events_by_depth_and_contract = tx_execution_info.events_by_depth_and_contract();
+
+    let n_events_at = |depth: usize, storage_address: &str| {
+        events_by_depth_and_contract
+            .get(&(depth, ContractAddress(patricia_key!(storage_address))))
+            .copied()
+    };
+    assert_eq!(n_events_at(0, "0x1"), Some(2));
+    assert_eq!(n_events_at(0, "0x2"), Some(1));
+    assert_eq!(n_events_at(1, "0x1"), Some(1));
+    assert_eq!(n_events_at(1, "0x2"), Some(3));
+    // Calls without events add no bucket.
+    assert_eq!(events_by_depth_and_contract.len(), 4);
+}
+
+#[test]
+fn test_avg_events_per_call() {
+    // 3 events in the outer call and 1 in its inner call.
+    let summary = call_info_with_x_events(3, 1).summarize();
+    assert_eq!(summary.avg_events_per_call(), 2.0);
+    assert_eq!(ExecutionSummary::default().avg_events_per_call(), 0.0);
+}
+
+#[cfg(feature = "summarize_async")]
+#[tokio::test]
+async fn test_summarize_all() {
+    let infos: Vec<TransactionExecutionInfo> = (1..=3)
+        .map(|n_events| TransactionExecutionInfo {
+            execute_call_info: Some(call_info_with_x_events(n_events, 0)),
+            ..Default::default()
+        })
+        .collect();
+    let sequential_summaries: Vec<ExecutionSummary> =
+        infos.iter().map(TransactionExecutionInfo::summarize).collect();
+
+    let summaries = summarize_all(infos).await;
+    assert_eq!(summaries, sequential_summaries);
+    let n_events: Vec<usize> =
+        summaries.iter().map(|summary| summary.event_summary.n_events).collect();
+    assert_eq!(n_events, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_widest_call() {
+    assert_eq!(TransactionExecutionInfo::default().widest_call(), (0, None));
+
+    let widest_call_info = call_info_with_x_events(0, 5);
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info_with_x_events(0, 2)),
+        execute_call_info: Some(CallInfo {
+            inner_calls: vec![call_info_with_x_events(0, 3), widest_call_info.clone()],
+            ..shared_call_info()
+        }),
+        ..Default::default()
+    };
+
+    assert_eq!(tx_execution_info.widest_call(), (5, Some(&widest_call_info)));
+}
+
+#[test]
+fn test_call_frames() {
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(call_info_with_deep_inner_calls(1, 2, 1, 1)),
+        ..Default::default()
+    };
+
+    let class_hash = class_hash!("0x1");
+    assert_eq!(
+        tx_execution_info.call_frames(),
+        vec![(0, class_hash), (1, class_hash), (2, class_hash), (1, class_hash), (2, class_hash)]
+    );
+}
+
+#[test]
+fn test_deepest_path() {
+    assert!(TransactionExecutionInfo::default().deepest_path().is_empty());
+
+    let tx_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info_with_x_events(0, 1)),
+        execute_call_info: Some(call_info_with_deep_inner_calls(1, 2, 1, 3)),
+        ..Default::default()
+    };
+
+    let deepest_path = tx_execution_info.deepest_path();
+    assert_eq!(deepest_path.len(), tx_execution_info.quick_stats().max_depth + 1);
+    assert_eq!(deepest_path, vec![class_hash!("0x1"); 3]);
+}
+
+#[test]
+fn test_constructor_and_regular_call_counts() {
+    let constructor_call = CallInfo {
+        call: CallEntryPoint {
+            entry_point_type: EntryPointType::Constructor,
+            ..shared_call_info().call
+        },
+        ..Default::default()
+    };
+    let tx_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(CallInfo {
+            inner_calls: vec![constructor_call, shared_call_info()],
+            ..shared_call_info()
+        }),
+        ..Default::default()
+    };
+
+    let summary = tx_execution_info.summarize();
+    assert_eq!(summary.constructor_call_count, 1);
+    assert_eq!(summary.regular_call_count, 2);
+}
+
+#[test]
+fn test_tree_display_bounded() {
+    let call_info = call_info_with_deep_inner_calls(0, 3, 0, 2);
+
+    let expected_display = "\
+0x1
+  0x1
+    ... (2 more)
+  0x1
+    ... (2 more)
+  ... (1 more)
+";
+    assert_eq!(call_info.tree_display_bounded(1, 2), expected_display);
+}
+
+#[test]
+fn test_structural_diff() {
+    let call_info = call_info_with_deep_inner_calls(1, 2, 1, 2);
+    assert!(call_info.structural_diff(&call_info.clone()).is_empty());
+
+    let mut other_call_info = call_info.clone();
+    other_call_info.inner_calls[1].inner_calls[0].execution.events.push(OrderedEvent::default());
+
+    assert_eq!(
+        call_info.structural_diff(&other_call_info),
+        vec![TreeDiff { path: vec![1, 0], kind: TreeDiffKind::EventCount { left: 1, right: 2 } }]
+    );
 }
@@ -298,6 +298,19 @@ fn expected_fee_transfer_call_info(
             sequencer_balance_key_low,
             sequencer_balance_key_high,
         ]),
+        read_storage_keys: HashSet::from_iter(vec![
+            sender_balance_key_low,
+            sender_balance_key_high,
+            sequencer_balance_key_low,
+            sequencer_balance_key_high,
+        ]),
+        // Both balances are then written.
+        written_storage_keys: HashSet::from_iter(vec![
+            sender_balance_key_low,
+            sender_balance_key_high,
+            sequencer_balance_key_low,
+            sequencer_balance_key_high,
+        ]),
         ..Default::default()
     })
 }
@@ -2079,6 +2092,7 @@ fn test_l1_handler(#[values(false, true)] use_kzg_da: bool) {
             builtin_instance_counter: HashMap::from([(BuiltinName::range_check, 6)]),
         },
         accessed_storage_keys: HashSet::from_iter(vec![accessed_storage_key]),
+        written_storage_keys: HashSet::from_iter(vec![accessed_storage_key]),
         tracked_resource: test_contract
             .get_class()
             .tracked_resource(&versioned_constants.min_compiler_version_for_sierra_gas),
@@ -17,6 +17,8 @@ pub enum ClientError {
     ResponseError(StatusCode, ServerError),
     #[error("Got an unexpected response type: {0}")]
     UnexpectedResponse(String),
+    #[error("The response has {size} bytes, more than the limit of {limit} bytes.")]
+    ResponseTooLarge { size: usize, limit: usize },
 }
 
 pub type ClientResult<T> = Result<T, ClientError>;
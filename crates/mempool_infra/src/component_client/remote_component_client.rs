@@ -4,9 +4,12 @@ use std::net::IpAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
-use hyper::body::to_bytes;
+use hyper::body::{to_bytes, HttpBody};
+use hyper::client::{Builder as ClientBuilder, HttpConnector, ResponseFuture};
 use hyper::header::CONTENT_TYPE;
 use hyper::{Body, Client, Request as HyperRequest, Response as HyperResponse, StatusCode, Uri};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use rustls::{Certificate, ClientConfig, PrivateKey, RootCertStore};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
@@ -26,6 +29,8 @@ use crate::serde_utils::BincodeSerdeWrapper;
 /// - `uri`: URI address of the server.
 /// - `client`: The inner HTTP client that initiates the connection to the server and manages it.
 /// - `config`: Client configuration.
+/// - `max_response_bytes`: The size limit of responses, if any; see
+///   [`RemoteComponentClient::with_max_response_bytes`].
 ///
 /// # Example
 /// ```rust
@@ -80,30 +85,160 @@ where
     Response: DeserializeOwned,
 {
     uri: Uri,
-    client: Client<hyper::client::HttpConnector>,
+    client: HttpClient,
     config: RemoteClientConfig,
+    interceptor: Option<ResponseInterceptor<Response>>,
+    max_response_bytes: Option<usize>,
     _req: PhantomData<Request>,
     _res: PhantomData<Response>,
 }
 
+// The inner HTTP client: plaintext, unless the client was built with a TLS configuration.
+#[derive(Clone)]
+enum HttpClient {
+    Plaintext(Client<HttpConnector>),
+    Tls(Client<HttpsConnector<HttpConnector>>),
+}
+
+impl HttpClient {
+    fn request(&self, http_request: HyperRequest<Body>) -> ResponseFuture {
+        match self {
+            Self::Plaintext(client) => client.request(http_request),
+            Self::Tls(client) => client.request(http_request),
+        }
+    }
+}
+
+/// A hook invoked on every response successfully decoded by a [`RemoteComponentClient`].
+pub type ResponseInterceptor<Response> = Arc<dyn Fn(&Response) + Send + Sync>;
+
+/// TLS configuration of a [`RemoteComponentClient`]. All certificates and keys are DER-encoded.
+#[derive(Clone, Debug, Default)]
+pub struct RemoteClientTlsConfig {
+    /// The certificates of the certificate authorities trusted to sign the server's certificate.
+    pub ca_certificates: Vec<Vec<u8>>,
+    /// The client's certificate chain and private key, for servers requiring client
+    /// authentication.
+    pub client_identity: Option<ClientIdentity>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ClientIdentity {
+    pub certificate_chain: Vec<Vec<u8>>,
+    pub private_key: Vec<u8>,
+}
+
+impl RemoteClientTlsConfig {
+    fn to_rustls_config(&self) -> Result<ClientConfig, rustls::Error> {
+        let mut root_store = RootCertStore::empty();
+        for ca_certificate in &self.ca_certificates {
+            root_store.add(&Certificate(ca_certificate.clone()))?;
+        }
+        let config_builder =
+            ClientConfig::builder().with_safe_defaults().with_root_certificates(root_store);
+
+        match &self.client_identity {
+            Some(ClientIdentity { certificate_chain, private_key }) => config_builder
+                .with_client_auth_cert(
+                    certificate_chain.iter().cloned().map(Certificate).collect(),
+                    PrivateKey(private_key.clone()),
+                ),
+            None => Ok(config_builder.with_no_client_auth()),
+        }
+    }
+}
+
+/// A builder of a [`RemoteComponentClient`]. The built client communicates in plaintext, unless a
+/// TLS configuration is provided.
+pub struct RemoteComponentClientBuilder<Request, Response> {
+    config: RemoteClientConfig,
+    tls_config: Option<RemoteClientTlsConfig>,
+    _req: PhantomData<Request>,
+    _res: PhantomData<Response>,
+}
+
+impl<Request, Response> RemoteComponentClientBuilder<Request, Response>
+where
+    Request: Serialize + DeserializeOwned + Debug + Clone,
+    Response: Serialize + DeserializeOwned + Debug,
+{
+    pub fn with_tls(mut self, tls_config: RemoteClientTlsConfig) -> Self {
+        self.tls_config = Some(tls_config);
+        self
+    }
+
+    pub fn build(self) -> Result<RemoteComponentClient<Request, Response>, rustls::Error> {
+        let Some(tls_config) = self.tls_config else {
+            return Ok(RemoteComponentClient::new(self.config));
+        };
+        let connector = HttpsConnectorBuilder::new()
+            .with_tls_config(tls_config.to_rustls_config()?)
+            .https_only()
+            .enable_http2()
+            .build();
+        let client = HttpClient::Tls(client_builder(&self.config).build(connector));
+        Ok(RemoteComponentClient::with_http_client(self.config, "https", client))
+    }
+}
+
+fn client_builder(config: &RemoteClientConfig) -> ClientBuilder {
+    let mut builder = Client::builder();
+    builder
+        .http2_only(true)
+        .pool_max_idle_per_host(config.idle_connections)
+        .pool_idle_timeout(Duration::from_secs(config.idle_timeout));
+    builder
+}
+
 impl<Request, Response> RemoteComponentClient<Request, Response>
 where
     Request: Serialize + DeserializeOwned + Debug + Clone,
     Response: Serialize + DeserializeOwned + Debug,
 {
     pub fn new(config: RemoteClientConfig) -> Self {
+        let client = HttpClient::Plaintext(client_builder(&config).build_http());
+        Self::with_http_client(config, "http", client)
+    }
+
+    fn with_http_client(config: RemoteClientConfig, scheme: &str, client: HttpClient) -> Self {
         let ip_address = config.socket.ip();
         let port = config.socket.port();
         let uri = match ip_address {
-            IpAddr::V4(ip_address) => format!("http://{}:{}/", ip_address, port).parse().unwrap(),
-            IpAddr::V6(ip_address) => format!("http://[{}]:{}/", ip_address, port).parse().unwrap(),
+            IpAddr::V4(ip_address) => format!("{scheme}://{ip_address}:{port}/").parse().unwrap(),
+            IpAddr::V6(ip_address) => format!("{scheme}://[{ip_address}]:{port}/").parse().unwrap(),
         };
-        let client = Client::builder()
-            .http2_only(true)
-            .pool_max_idle_per_host(config.idle_connections)
-            .pool_idle_timeout(Duration::from_secs(config.idle_timeout))
-            .build_http();
-        Self { uri, client, config, _req: PhantomData, _res: PhantomData }
+        Self {
+            uri,
+            client,
+            config,
+            interceptor: None,
+            max_response_bytes: None,
+            _req: PhantomData,
+            _res: PhantomData,
+        }
+    }
+
+    pub fn builder(config: RemoteClientConfig) -> RemoteComponentClientBuilder<Request, Response> {
+        RemoteComponentClientBuilder {
+            config,
+            tls_config: None,
+            _req: PhantomData,
+            _res: PhantomData,
+        }
+    }
+
+    /// Installs a hook that observes every decoded response, e.g., for metrics or testing.
+    pub fn with_interceptor(mut self, interceptor: ResponseInterceptor<Response>) -> Self {
+        self.interceptor = Some(interceptor);
+        self
+    }
+
+    /// Rejects responses larger than the given number of bytes with
+    /// [`ClientError::ResponseTooLarge`], without reading them in full, e.g., to protect against
+    /// a misbehaving server exhausting the client's memory.
+    pub fn with_max_response_bytes(mut self, max_response_bytes: usize) -> Self {
+        self.max_response_bytes = Some(max_response_bytes);
+        self
     }
 
     pub async fn send(&self, component_request: Request) -> ClientResult<Response> {
@@ -112,7 +247,8 @@ where
         for _ in 0..self.config.retries {
             let http_request = self.construct_http_request(component_request.clone());
             let res = self.try_send(http_request).await;
-            if res.is_ok() {
+            // A response too large would be as large when retried.
+            if matches!(res, Ok(_) | Err(ClientError::ResponseTooLarge { .. })) {
                 return res;
             }
         }
@@ -122,6 +258,13 @@ where
         self.try_send(http_request).await
     }
 
+    /// Sends the request once, regardless of the configured number of retries, e.g., for requests
+    /// whose effect must not be repeated.
+    pub async fn send_once(&self, component_request: Request) -> ClientResult<Response> {
+        let http_request = self.construct_http_request(component_request);
+        self.try_send(http_request).await
+    }
+
     fn construct_http_request(&self, component_request: Request) -> HyperRequest<Body> {
         HyperRequest::post(self.uri.clone())
             .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
@@ -141,27 +284,60 @@ where
             .map_err(|e| ClientError::CommunicationFailure(Arc::new(e)))?;
 
         match http_response.status() {
-            StatusCode::OK => get_response_body(http_response).await,
+            StatusCode::OK => {
+                let response = get_response_body(http_response, self.max_response_bytes).await?;
+                if let Some(interceptor) = &self.interceptor {
+                    interceptor(&response);
+                }
+                Ok(response)
+            }
             status_code => Err(ClientError::ResponseError(
                 status_code,
-                get_response_body(http_response).await?,
+                get_response_body(http_response, self.max_response_bytes).await?,
             )),
         }
     }
 }
 
-async fn get_response_body<Response>(response: HyperResponse<Body>) -> Result<Response, ClientError>
+async fn get_response_body<Response>(
+    response: HyperResponse<Body>,
+    max_response_bytes: Option<usize>,
+) -> Result<Response, ClientError>
 where
     Response: Serialize + DeserializeOwned + Debug,
 {
-    let body_bytes = to_bytes(response.into_body())
-        .await
-        .map_err(|e| ClientError::ResponseParsingFailure(Arc::new(e)))?;
+    let body_bytes = match max_response_bytes {
+        Some(limit) => to_bytes_bounded(response.into_body(), limit).await?,
+        None => to_bytes(response.into_body())
+            .await
+            .map_err(|e| ClientError::ResponseParsingFailure(Arc::new(e)))?
+            .to_vec(),
+    };
 
     BincodeSerdeWrapper::<Response>::from_bincode(&body_bytes)
         .map_err(|e| ClientError::ResponseDeserializationFailure(Arc::new(e)))
 }
 
+// Reads the body, failing as soon as it is known to exceed the limit: either by its declared size
+// or once more than the limit was received.
+async fn to_bytes_bounded(mut body: Body, limit: usize) -> Result<Vec<u8>, ClientError> {
+    let declared_size = usize::try_from(body.size_hint().lower()).unwrap_or(usize::MAX);
+    if declared_size > limit {
+        return Err(ClientError::ResponseTooLarge { size: declared_size, limit });
+    }
+
+    let mut body_bytes = Vec::with_capacity(declared_size);
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| ClientError::ResponseParsingFailure(Arc::new(e)))?;
+        let size = body_bytes.len() + chunk.len();
+        if size > limit {
+            return Err(ClientError::ResponseTooLarge { size, limit });
+        }
+        body_bytes.extend_from_slice(&chunk);
+    }
+    Ok(body_bytes)
+}
+
 // Can't derive because derive forces the generics to also be `Clone`, which we prefer not to do
 // since it'll require the generic Request and Response types to be cloneable.
 impl<Request, Response> Clone for RemoteComponentClient<Request, Response>
@@ -174,6 +350,8 @@ where
             uri: self.uri.clone(),
             client: self.client.clone(),
             config: self.config.clone(),
+            interceptor: self.interceptor.clone(),
+            max_response_bytes: self.max_response_bytes,
             _req: PhantomData,
             _res: PhantomData,
         }
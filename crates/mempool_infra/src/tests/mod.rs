@@ -1,5 +1,6 @@
 mod local_component_client_server_test;
 mod remote_component_client_server_test;
+mod remote_component_client_tls_test;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::Felt;
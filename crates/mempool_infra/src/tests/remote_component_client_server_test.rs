@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -206,6 +207,26 @@ async fn test_proper_setup() {
     test_a_b_functionality(a_remote_client, b_remote_client, setup_value).await;
 }
 
+#[tokio::test]
+async fn test_response_interceptor() {
+    let a_socket = get_available_socket().await;
+    let b_socket = get_available_socket().await;
+    setup_for_tests(VALID_VALUE_A, a_socket, b_socket).await;
+
+    let n_responses = Arc::new(AtomicUsize::new(0));
+    let n_responses_clone = n_responses.clone();
+    let a_client_config = RemoteClientConfig { socket: a_socket, ..Default::default() };
+    let a_remote_client =
+        ComponentAClient::new(a_client_config).with_interceptor(Arc::new(move |_response| {
+            n_responses_clone.fetch_add(1, Ordering::Relaxed);
+        }));
+
+    for _ in 0..3 {
+        assert_eq!(a_remote_client.a_get_value().await.unwrap(), VALID_VALUE_A);
+    }
+    assert_eq!(n_responses.load(Ordering::Relaxed), 3);
+}
+
 #[tokio::test]
 async fn test_faulty_client_setup() {
     let a_socket = get_available_socket().await;
@@ -332,4 +353,12 @@ async fn test_retry_request() {
     let a_client_no_retry = ComponentAClient::new(no_retry_config);
     let expected_error_contained_keywords = [StatusCode::IM_A_TEAPOT.as_str()];
     verify_error(a_client_no_retry.clone(), &expected_error_contained_keywords).await;
+
+    // The current server state is 'true', hence the attempt succeeds and sets the server state to
+    // 'false'. A request sent once is then not retried, regardless of the client's config.
+    assert_eq!(a_client_retry.a_get_value().await.unwrap(), VALID_VALUE_A);
+    let Err(error) = a_client_retry.send_once(ComponentARequest::AGetValue).await else {
+        panic!("Expected an error.");
+    };
+    assert_error_contains_keywords(error.to_string(), &expected_error_contained_keywords);
 }
@@ -0,0 +1,88 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Response};
+use rustls::{Certificate, PrivateKey, ServerConfig};
+use starknet_types_core::felt::Felt;
+use tokio::net::TcpListener;
+use tokio::task;
+use tokio_rustls::TlsAcceptor;
+
+use crate::component_client::{ClientError, RemoteClientTlsConfig, RemoteComponentClient};
+use crate::component_definitions::RemoteClientConfig;
+use crate::serde_utils::BincodeSerdeWrapper;
+use crate::test_utils::get_available_socket;
+use crate::tests::{ComponentARequest, ComponentAResponse, ValueA};
+
+type ComponentAClient = RemoteComponentClient<ComponentARequest, ComponentAResponse>;
+
+const VALUE_A: ValueA = Felt::ONE;
+
+/// Returns a DER-encoded self-signed certificate for the loopback address, and its private key.
+fn self_signed_certificate() -> (Vec<u8>, Vec<u8>) {
+    let certificate = rcgen::generate_simple_self_signed(vec!["127.0.0.1".to_string()]).unwrap();
+    (certificate.serialize_der().unwrap(), certificate.serialize_private_key_der())
+}
+
+/// Starts a TLS server that answers every request with `VALUE_A`.
+async fn start_tls_server(socket: SocketAddr, certificate: Vec<u8>, private_key: Vec<u8>) {
+    let server_config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(vec![Certificate(certificate)], PrivateKey(private_key))
+        .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(server_config));
+    let listener = TcpListener::bind(socket).await.unwrap();
+
+    task::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let acceptor = acceptor.clone();
+            task::spawn(async move {
+                // Failed handshakes (e.g., of plaintext clients) just drop the connection.
+                let Ok(tls_stream) = acceptor.accept(stream).await else {
+                    return;
+                };
+                let service = service_fn(|_request| async {
+                    let body = BincodeSerdeWrapper::new(ComponentAResponse::AGetValue(VALUE_A))
+                        .to_bincode()
+                        .unwrap();
+                    Ok::<_, hyper::Error>(Response::new(Body::from(body)))
+                });
+                let _ = Http::new().http2_only(true).serve_connection(tls_stream, service).await;
+            });
+        }
+    });
+}
+
+#[tokio::test]
+async fn tls_client_connects_to_tls_server() {
+    let socket = get_available_socket().await;
+    let (certificate, private_key) = self_signed_certificate();
+    start_tls_server(socket, certificate.clone(), private_key).await;
+
+    let tls_config =
+        RemoteClientTlsConfig { ca_certificates: vec![certificate], ..Default::default() };
+    let client = ComponentAClient::builder(RemoteClientConfig { socket, ..Default::default() })
+        .with_tls(tls_config)
+        .build()
+        .unwrap();
+
+    let response = client.send(ComponentARequest::AGetValue).await;
+    assert_matches!(response, Ok(ComponentAResponse::AGetValue(value)) if value == VALUE_A);
+}
+
+#[tokio::test]
+async fn plaintext_client_fails_to_connect_to_tls_server() {
+    let socket = get_available_socket().await;
+    let (certificate, private_key) = self_signed_certificate();
+    start_tls_server(socket, certificate, private_key).await;
+
+    let client = ComponentAClient::new(RemoteClientConfig { socket, ..Default::default() });
+
+    let response = client.send(ComponentARequest::AGetValue).await;
+    assert_matches!(response, Err(ClientError::CommunicationFailure(_)));
+}
@@ -24,10 +24,12 @@ use papyrus_protobuf::consensus::{ConsensusMessage, Vote};
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::executable_transaction::Transaction;
 use starknet_batcher_types::batcher_types::{
+    BlockResourceBounds,
     BuildProposalInput,
     DecisionReachedInput,
     GetProposalContent,
     GetProposalContentInput,
+    Priority,
     ProposalId,
     ProposalStatus,
     SendProposalContent,
@@ -48,6 +50,9 @@ type HeightToIdToContent =
 // TODO(matan): Consider making this configurable. May want to define `max_proposal_parts`.
 const CHANNEL_SIZE: usize = 5000;
 
+// The time to wait before asking again for the content of a proposal that has none yet.
+const SLEEP_BETWEEN_EMPTY_CONTENT_REQUESTS: Duration = Duration::from_millis(10);
+
 pub struct SequencerConsensusContext {
     batcher: Arc<dyn BatcherClient>,
     validators: Vec<ValidatorId>,
@@ -101,6 +106,11 @@ impl ConsensusContext for SequencerConsensusContext {
             deadline: chrono::Utc::now() + timeout,
             // TODO: This is not part of Milestone 1.
             retrospective_block_hash: None,
+            // As the proposer, this build takes precedence over any other pending work.
+            priority: Priority::High,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
         };
         self.maybe_start_height(height).await;
         // TODO: Should we be returning an error?
@@ -267,14 +277,16 @@ async fn stream_build_proposal(
 ) {
     let mut content = Vec::new();
     loop {
-        let response =
-            match batcher.get_proposal_content(GetProposalContentInput { proposal_id }).await {
-                Ok(response) => response,
-                Err(e) => {
-                    warn!("Failed to get proposal content: {e:?}");
-                    return;
-                }
-            };
+        let response = match batcher
+            .get_proposal_content(GetProposalContentInput { proposal_id, resume_from: None })
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                warn!("Failed to get proposal content: {e:?}");
+                return;
+            }
+        };
         match response.content {
             GetProposalContent::Txs(txs) => {
                 content.extend_from_slice(&txs[..]);
@@ -302,6 +314,11 @@ async fn stream_build_proposal(
                 }
                 return;
             }
+            GetProposalContent::Empty => {
+                // The proposal has no content available yet, ask again shortly.
+                debug!("Proposal {proposal_id} has no content yet.");
+                tokio::time::sleep(SLEEP_BETWEEN_EMPTY_CONTENT_REQUESTS).await;
+            }
         }
     }
 }
@@ -62,7 +62,11 @@ async fn build_proposal() {
     let proposal_id_clone = Arc::clone(&proposal_id);
     batcher.expect_get_proposal_content().times(1).returning(move |input| {
         assert_eq!(input.proposal_id, *proposal_id_clone.get().unwrap());
-        Ok(GetProposalContentResponse { content: GetProposalContent::Txs(TX_BATCH.clone()) })
+        Ok(GetProposalContentResponse {
+            content: GetProposalContent::Txs(TX_BATCH.clone()),
+            sequence: 0,
+            is_last: false,
+        })
     });
     let proposal_id_clone = Arc::clone(&proposal_id);
     batcher.expect_get_proposal_content().times(1).returning(move |input| {
@@ -71,6 +75,8 @@ async fn build_proposal() {
             content: GetProposalContent::Finished(ProposalCommitment {
                 state_diff_commitment: STATE_DIFF_COMMITMENT,
             }),
+            sequence: 1,
+            is_last: true,
         })
     });
     let mut context = SequencerConsensusContext::new(Arc::new(batcher), NUM_VALIDATORS);
@@ -17,20 +17,27 @@ pub struct EventLeafElement {
     pub(crate) transaction_hash: TransactionHash,
 }
 
-/// Returns the root of a Patricia tree where each leaf is an event hash.
+impl EventLeafElement {
+    pub fn new(event: Event, transaction_hash: TransactionHash) -> Self {
+        Self { event, transaction_hash }
+    }
+}
+
+/// Returns the root of a Patricia tree where each leaf is an event hash; both the leaves and the
+/// tree are hashed with H.
 pub fn calculate_event_commitment<H: StarkHash>(
     event_leaf_elements: &[EventLeafElement],
 ) -> EventCommitment {
-    let event_leaves = event_leaf_elements.iter().map(calculate_event_hash).collect();
+    let event_leaves = event_leaf_elements.iter().map(calculate_event_hash::<H>).collect();
     EventCommitment(calculate_root::<H>(event_leaves))
 }
 
-// Poseidon(
+// H(
 //    from_address, transaction_hash,
 //    num_keys, key0, key1, ...,
 //    num_contents, content0, content1, ...
 // ).
-fn calculate_event_hash(event_leaf_element: &EventLeafElement) -> Felt {
+fn calculate_event_hash<H: StarkHash>(event_leaf_element: &EventLeafElement) -> Felt {
     let keys = &event_leaf_element.event.content.keys.iter().map(|k| k.0).collect::<Vec<Felt>>();
     let data = &event_leaf_element.event.content.data.0;
     HashChain::new()
@@ -38,5 +45,5 @@ fn calculate_event_hash(event_leaf_element: &EventLeafElement) -> Felt {
         .chain(&event_leaf_element.transaction_hash.0)
         .chain_size_and_elements(keys)
         .chain_size_and_elements(data)
-        .get_poseidon_hash()
+        .get_hash::<H>()
 }
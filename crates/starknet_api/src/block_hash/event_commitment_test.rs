@@ -3,6 +3,7 @@ use starknet_types_core::hash::Poseidon;
 
 use super::{calculate_event_commitment, calculate_event_hash, EventLeafElement};
 use crate::core::{ContractAddress, EventCommitment, PatriciaKey};
+use crate::hash::Keccak;
 use crate::transaction::{Event, EventContent, EventData, EventKey, TransactionHash};
 use crate::{contract_address, felt, patricia_key};
 
@@ -25,7 +26,29 @@ fn test_event_hash_regression() {
 
     let expected_hash = felt!("0x367807f532742a4dcbe2d8a47b974b22dd7496faa75edc64a3a5fdb6709057");
 
-    assert_eq!(expected_hash, calculate_event_hash(&event_leaf_element));
+    assert_eq!(expected_hash, calculate_event_hash::<Poseidon>(&event_leaf_element));
+}
+
+#[test]
+fn test_keccak_event_commitment_regression() {
+    let event_leaf_elements =
+        [get_event_leaf_element(0), get_event_leaf_element(1), get_event_leaf_element(2)];
+
+    let expected_root = felt!("0x0280add51b22076dd4c0de6f5b8c64e8947e36397245f73fb97be3f9946a2d79");
+
+    assert_eq!(
+        EventCommitment(expected_root),
+        calculate_event_commitment::<Keccak>(&event_leaf_elements),
+    );
+}
+
+#[test]
+fn test_keccak_event_hash_regression() {
+    let event_leaf_element = get_event_leaf_element(2);
+
+    let expected_hash = felt!("0x035ccfab37290e135d6f844caa8471209c70e40c3d0624dc42e7ec43ec2b3eb7");
+
+    assert_eq!(expected_hash, calculate_event_hash::<Keccak>(&event_leaf_element));
 }
 
 fn get_event_leaf_element(seed: u8) -> EventLeafElement {
@@ -118,4 +118,9 @@ impl HashChain {
     pub fn get_poseidon_hash(&self) -> StarkHash {
         Poseidon::hash_array(self.elements.as_slice())
     }
+
+    // Returns the hash of the chained felts with the given hash function.
+    pub fn get_hash<H: CoreStarkHash>(&self) -> StarkHash {
+        H::hash_array(self.elements.as_slice())
+    }
 }
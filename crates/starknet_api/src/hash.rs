@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::StarkHash as CoreStarkHash;
 
 pub type StarkHash = Felt;
 
@@ -20,6 +21,25 @@ pub fn starknet_keccak_hash(input: &[u8]) -> Felt {
     Felt::from_bytes_be(&hashed_bytes)
 }
 
+/// Starknet Keccak over the big-endian encoding of the felts; an alternative to Poseidon for
+/// commitments verified on L1, where Keccak is cheap.
+pub struct Keccak;
+
+impl CoreStarkHash for Keccak {
+    fn hash(felt_0: &Felt, felt_1: &Felt) -> Felt {
+        Self::hash_array(&[*felt_0, *felt_1])
+    }
+
+    fn hash_array(felts: &[Felt]) -> Felt {
+        let bytes: Vec<u8> = felts.iter().flat_map(Felt::to_bytes_be).collect();
+        starknet_keccak_hash(&bytes)
+    }
+
+    fn hash_single(felt: &Felt) -> Felt {
+        Self::hash_array(&[*felt])
+    }
+}
+
 #[cfg(any(feature = "testing", test))]
 pub struct FeltConverter;
 
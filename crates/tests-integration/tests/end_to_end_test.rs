@@ -3,7 +3,13 @@ use pretty_assertions::assert_eq;
 use rstest::{fixture, rstest};
 use starknet_api::block::BlockNumber;
 use starknet_api::transaction::TransactionHash;
-use starknet_batcher_types::batcher_types::{BuildProposalInput, ProposalId, StartHeightInput};
+use starknet_batcher_types::batcher_types::{
+    BlockResourceBounds,
+    BuildProposalInput,
+    Priority,
+    ProposalId,
+    StartHeightInput,
+};
 use starknet_batcher_types::communication::SharedBatcherClient;
 use starknet_mempool_integration_tests::integration_test_setup::IntegrationTestSetup;
 use starknet_mempool_integration_tests::integration_test_utils::create_integration_test_tx_generator;
@@ -77,6 +83,10 @@ pub async fn run_consensus_for_end_to_end_test(batcher_client: &SharedBatcherCli
             proposal_id,
             deadline: chrono::Utc::now() + build_proposal_duaration,
             retrospective_block_hash,
+            priority: Priority::Normal,
+            metadata: vec![],
+            max_transactions: None,
+            resource_bounds: BlockResourceBounds::default(),
         })
         .await
         .unwrap();